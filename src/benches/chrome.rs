@@ -0,0 +1,13 @@
+use tracing::Subscriber;
+use tracing_chrome::{ChromeLayer, ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Layer recording spans (with their fields) as a Chrome trace, `trace-<timestamp>.json`, which is
+/// viewable in chrome://tracing or Perfetto. The trace is flushed when the returned guard is
+/// dropped, so hold it for as long as spans should be recorded.
+pub fn chrome_layer<S>() -> (ChromeLayer<S>, FlushGuard)
+where
+  S: Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+{
+  ChromeLayerBuilder::new().include_args(true).build()
+}
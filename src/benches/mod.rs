@@ -1 +1,3 @@
 pub mod bench;
+#[cfg(feature = "chrome")]
+pub mod chrome;
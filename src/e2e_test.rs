@@ -4,11 +4,13 @@ use merlin::Transcript;
 use crate::{
   lasso::{
     densified::DensifiedRepresentation,
-    surge::{SparsePolyCommitmentGens, SparsePolynomialEvaluationProof},
+    surge::{
+      SparsePolyCommitmentGens, SparsePolynomialCommitment, SparsePolynomialEvaluationProof,
+    },
   },
   subtables::{
     and::AndSubtableStrategy, lt::LTSubtableStrategy, range_check::RangeCheckSubtableStrategy,
-    SubtableStrategy,
+    SubtableStrategy, Subtables,
   },
   utils::math::Math,
   utils::random::RandomTape,
@@ -18,8 +20,8 @@ macro_rules! e2e_test {
   ($test_name:ident, $Strategy:ty, $G:ty, $F:ty, $C:expr, $M:expr, $sparsity:expr) => {
     #[test]
     fn $test_name() {
+      use crate::lasso::densified::padded_sparsity;
       use crate::utils::test::{gen_indices, gen_random_point};
-      use ark_std::log2;
 
       const C: usize = $C;
       const M: usize = $M;
@@ -27,7 +29,7 @@ macro_rules! e2e_test {
       // parameters
       const NUM_MEMORIES: usize = <$Strategy as SubtableStrategy<$F, C, M>>::NUM_MEMORIES;
       let log_M: usize = M.log_2();
-      let log_s: usize = log2($sparsity) as usize;
+      let log_s: usize = padded_sparsity($sparsity).log_2();
 
       // generate sparse polynomial
       let nz: Vec<[usize; C]> = gen_indices($sparsity, M);
@@ -79,6 +81,42 @@ e2e_test!(
   /* M= */ 16,
   /* sparsity= */ 128
 );
+e2e_test!(
+  prove_4d_lt_odd_s,
+  LTSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 17
+);
+e2e_test!(
+  prove_4d_lt_single_lookup,
+  LTSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 1
+);
+e2e_test!(
+  prove_4d_lt_no_lookups,
+  LTSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 4,
+  /* M= */ 16,
+  /* sparsity= */ 0
+);
+e2e_test!(
+  prove_2d_lt_two_lookups,
+  LTSubtableStrategy,
+  G1Projective,
+  Fr,
+  /* C= */ 2,
+  /* M= */ 4,
+  /* sparsity= */ 2
+);
 e2e_test!(
   prove_4d_and,
   AndSubtableStrategy,
@@ -97,3 +135,492 @@ e2e_test!(
   /* M= */ 256,
   /* sparsity= */ 16
 );
+e2e_test!(
+  prove_3d_range_odd_s,
+  RangeCheckSubtableStrategy::<40>,
+  G1Projective,
+  Fr,
+  /* C= */ 3,
+  /* M= */ 256,
+  /* sparsity= */ 17
+);
+
+// Parameters of the fixed instance shared by the tests below
+const C: usize = 4;
+const M: usize = 16;
+const S: usize = 16;
+type LTProof = SparsePolynomialEvaluationProof<G1Projective, C, M, LTSubtableStrategy>;
+
+/// Random lookups into `Strategy`'s C = 4, M = 16 tables, generators sized for s = 16 of them, and
+/// a random evaluation point.
+fn instance<Strategy: SubtableStrategy<Fr, C, M>>() -> (
+  DensifiedRepresentation<Fr, C>,
+  SparsePolyCommitmentGens<G1Projective>,
+  Vec<Fr>,
+) {
+  use crate::utils::test::{gen_indices, gen_random_point};
+
+  let log_M: usize = M.log_2();
+  let nz: Vec<[usize; C]> = gen_indices(S, M);
+  let dense: DensifiedRepresentation<Fr, C> =
+    DensifiedRepresentation::from_lookup_indices(&nz, log_M);
+  let num_memories = <Strategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  let gens =
+    SparsePolyCommitmentGens::<G1Projective>::new(b"gens_sparse_poly", C, S, num_memories, log_M);
+  let r: Vec<Fr> = gen_random_point(S.log_2());
+  (dense, gens, r)
+}
+
+/// A `LTSubtableStrategy` instance, its commitment, and a proof of it under the transcript label
+/// `b"example"`.
+fn lt_fixture() -> (
+  LTProof,
+  SparsePolynomialCommitment<G1Projective>,
+  Vec<Fr>,
+  SparsePolyCommitmentGens<G1Projective>,
+  DensifiedRepresentation<Fr, C>,
+) {
+  let (mut dense, gens, r) = instance::<LTSubtableStrategy>();
+  let commitment = dense.commit::<G1Projective>(&gens);
+
+  let mut random_tape = RandomTape::new(b"proof");
+  let mut prover_transcript = Transcript::new(b"example");
+  let proof = LTProof::prove(
+    &mut dense,
+    &r,
+    &gens,
+    &mut prover_transcript,
+    &mut random_tape,
+  );
+  (proof, commitment, r, gens, dense)
+}
+
+#[test]
+fn mismatched_parameters() {
+  use crate::utils::errors::ProofVerifyError;
+  use crate::utils::test::gen_random_point;
+
+  let (proof, commitment, r, gens, dense) = lt_fixture();
+
+  // Evaluation point with one coordinate too many
+  let r_long: Vec<Fr> = gen_random_point(S.log_2() + 1);
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify(&commitment, &r_long, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::InvalidInputLength(4, 5))
+  ));
+
+  // Commitment header claiming a non-power-of-two sparsity
+  let mut bad_commitment = dense.commit::<G1Projective>(&gens);
+  bad_commitment.s = S + 1;
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify(&bad_commitment, &r, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::InvalidSparsity(17))
+  ));
+}
+
+#[test]
+fn security_level() {
+  use crate::utils::errors::ProofVerifyError;
+
+  // Curve25519's scalar field is ~2^252, and the union bound for a toy instance is tiny
+  let security_bits = LTProof::security_bits(S).unwrap();
+  assert!(security_bits > 230.0 && security_bits < 252.0);
+  // More lookups can only increase the soundness error
+  assert!(LTProof::security_bits(1 << 20).unwrap() < security_bits);
+  // Fewer than two lookups are padded exactly as densification pads them
+  let two_lookups = LTProof::security_bits(2).unwrap();
+  assert_eq!(LTProof::security_bits(0).unwrap(), two_lookups);
+  assert_eq!(LTProof::security_bits(1).unwrap(), two_lookups);
+  // A sparsity too large to pad has no defined security level
+  assert!(matches!(
+    LTProof::security_bits(usize::MAX),
+    Err(ProofVerifyError::InvalidSparsity(usize::MAX))
+  ));
+
+  let (proof, commitment, r, gens, _) = lt_fixture();
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify_with_security_level(&commitment, &r, &gens, &mut verifier_transcript, 300),
+    Err(ProofVerifyError::InsufficientSecurity(300, _))
+  ));
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(proof
+    .verify_with_security_level(&commitment, &r, &gens, &mut verifier_transcript, 128)
+    .is_ok());
+
+  // A sparsity header too large to pad is rejected before the security estimate is computed
+  let mut bad_commitment = commitment;
+  bad_commitment.s = usize::MAX;
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify_with_security_level(&bad_commitment, &r, &gens, &mut verifier_transcript, 128),
+    Err(ProofVerifyError::InvalidSparsity(usize::MAX))
+  ));
+}
+
+#[test]
+fn transcript_digests() {
+  use crate::utils::errors::ProofVerifyError;
+
+  let (proof, commitment, r, gens, _) = lt_fixture();
+  let digests = proof.transcript_digests();
+  assert_ne!(digests.post_commitments, digests.post_sumcheck);
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(proof
+    .verify(&commitment, &r, &gens, &mut verifier_transcript)
+    .is_ok());
+
+  // A session started under a different label diverges at the first checkpoint
+  let mut verifier_transcript = Transcript::new(b"other session");
+  assert!(matches!(
+    proof.verify(&commitment, &r, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::TranscriptDigestMismatch(
+      "post_commitments"
+    ))
+  ));
+}
+
+#[test]
+fn tampered_transcript_digests() {
+  use crate::utils::errors::ProofVerifyError;
+
+  let (mut proof, commitment, r, gens, _) = lt_fixture();
+
+  proof.transcript_digests.post_sumcheck[0] ^= 1;
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify(&commitment, &r, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::TranscriptDigestMismatch("post_sumcheck"))
+  ));
+
+  // The earlier checkpoint is checked first
+  proof.transcript_digests.post_commitments[0] ^= 1;
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify(&commitment, &r, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::TranscriptDigestMismatch(
+      "post_commitments"
+    ))
+  ));
+}
+
+#[test]
+fn transcript_domains_never_repeat() {
+  use crate::utils::transcript::{ProtocolDomain, APPENDED_DOMAINS};
+
+  APPENDED_DOMAINS.with(|domains| domains.borrow_mut().clear());
+  let (proof, commitment, r, gens, _) = lt_fixture();
+  let proved = APPENDED_DOMAINS.with(|domains| domains.take());
+
+  for (i, domain) in proved.iter().enumerate() {
+    assert!(
+      !proved[i + 1..].contains(domain),
+      "{domain:?} is appended more than once"
+    );
+  }
+  // The primary sumcheck, plus one per layer of the ops and mem grand products
+  let num_sumchecks = proved
+    .iter()
+    .filter(|(domain, _)| *domain == ProtocolDomain::Sumcheck)
+    .count();
+  assert_eq!(num_sumchecks, 1 + S.log_2() + M.log_2());
+
+  // The verifier opens the same domains in the same order
+  let mut verifier_transcript = Transcript::new(b"example");
+  proof
+    .verify(&commitment, &r, &gens, &mut verifier_transcript)
+    .unwrap();
+  assert_eq!(APPENDED_DOMAINS.with(|domains| domains.take()), proved);
+}
+
+#[test]
+fn verify_with_profiling() {
+  let (proof, commitment, r, gens, _) = lt_fixture();
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  let profile = proof
+    .verify_with_profiling(&commitment, &r, &gens, &mut verifier_transcript)
+    .unwrap();
+  assert_eq!(profile.proof_size, proof.compressed_size());
+  assert!(profile.memory_checking > std::time::Duration::ZERO);
+}
+
+#[test]
+fn shared_subtables() {
+  use std::sync::Arc;
+
+  // Every caller gets the same, once-materialized copy
+  let materialized = Subtables::<Fr, C, M, LTSubtableStrategy>::shared_materialization();
+  assert!(Arc::ptr_eq(
+    &materialized,
+    &Subtables::<Fr, C, M, LTSubtableStrategy>::shared_materialization()
+  ));
+
+  std::thread::scope(|scope| {
+    for _ in 0..2 {
+      let materialized = materialized.clone();
+      scope.spawn(move || {
+        let (mut dense, gens, r) = instance::<LTSubtableStrategy>();
+        let commitment = dense.commit::<G1Projective>(&gens);
+
+        let mut random_tape = RandomTape::new(b"proof");
+        let mut prover_transcript = Transcript::new(b"example");
+        let proof = LTProof::prove_with_subtables(
+          &mut dense,
+          &r,
+          &gens,
+          &mut prover_transcript,
+          &mut random_tape,
+          materialized,
+        );
+
+        let mut verifier_transcript = Transcript::new(b"example");
+        assert!(proof
+          .verify(&commitment, &r, &gens, &mut verifier_transcript)
+          .is_ok());
+      });
+    }
+  });
+
+  // Clearing drops only the cache's reference; the next lookup materializes a fresh copy
+  Subtables::<Fr, C, M, LTSubtableStrategy>::clear_materialized();
+  assert!(!Arc::ptr_eq(
+    &materialized,
+    &Subtables::<Fr, C, M, LTSubtableStrategy>::shared_materialization()
+  ));
+  Subtables::<Fr, C, M, LTSubtableStrategy>::clear_materialized();
+}
+
+#[test]
+fn prove_and_verify() {
+  let (mut dense, gens, r) = instance::<LTSubtableStrategy>();
+  let (proof, commitment) = LTProof::prove_and_verify(&mut dense, &r, &gens, b"example").unwrap();
+
+  // The returned proof and commitment verify independently as well
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(proof
+    .verify(&commitment, &r, &gens, &mut verifier_transcript)
+    .is_ok());
+}
+
+#[test]
+fn prove_cancellable() {
+  use crate::utils::errors::Cancelled;
+  use std::sync::atomic::{AtomicBool, Ordering};
+
+  let (mut dense, gens, r) = instance::<LTSubtableStrategy>();
+  let commitment = dense.commit::<G1Projective>(&gens);
+  let cancel = AtomicBool::new(false);
+
+  // Left unset, the flag doesn't change the proof
+  let mut random_tape = RandomTape::new(b"proof");
+  let mut prover_transcript = Transcript::new(b"example");
+  let proof = LTProof::prove_cancellable(
+    &mut dense,
+    &r,
+    &gens,
+    &mut prover_transcript,
+    &mut random_tape,
+    &cancel,
+  )
+  .unwrap();
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(proof
+    .verify(&commitment, &r, &gens, &mut verifier_transcript)
+    .is_ok());
+
+  cancel.store(true, Ordering::Relaxed);
+  let mut random_tape = RandomTape::new(b"proof");
+  let mut prover_transcript = Transcript::new(b"example");
+  assert_eq!(
+    LTProof::prove_cancellable(
+      &mut dense,
+      &r,
+      &gens,
+      &mut prover_transcript,
+      &mut random_tape,
+      &cancel,
+    )
+    .unwrap_err(),
+    Cancelled
+  );
+}
+
+/// Identity table whose MLE is deliberately off by one, so memory-checking's init check fails.
+enum BrokenMleStrategy {}
+
+impl<const C: usize, const M: usize> SubtableStrategy<Fr, C, M> for BrokenMleStrategy {
+  const NUM_SUBTABLES: usize = 1;
+  const NUM_MEMORIES: usize = C;
+
+  fn materialize_subtables() -> [Vec<Fr>; <Self as SubtableStrategy<Fr, C, M>>::NUM_SUBTABLES] {
+    [(0..M).map(|i| Fr::from(i as u64)).collect()]
+  }
+
+  fn evaluate_subtable_mle(_: usize, point: &[Fr]) -> Fr {
+    let b = point.len();
+    let mut result = Fr::from(1u64);
+    for i in 0..b {
+      result += Fr::from(1u64 << i) * point[b - i - 1];
+    }
+    result
+  }
+
+  fn combine_lookups(vals: &[Fr; <Self as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES]) -> Fr {
+    let log_m = M.log_2();
+    let mut sum = Fr::from(0u64);
+    for (i, val) in vals.iter().enumerate() {
+      sum += Fr::from(1u64 << (i * log_m)) * val;
+    }
+    sum
+  }
+
+  fn g_poly_degree() -> usize {
+    1
+  }
+}
+
+#[test]
+fn prove_and_verify_reports_failing_subproof() {
+  use crate::utils::errors::ProofVerifyError;
+
+  type Proof = SparsePolynomialEvaluationProof<G1Projective, C, M, BrokenMleStrategy>;
+
+  let (mut dense, gens, r) = instance::<BrokenMleStrategy>();
+  assert!(matches!(
+    Proof::prove_and_verify(&mut dense, &r, &gens, b"example"),
+    Err(ProofVerifyError::HashLayerFailed(0, "init"))
+  ));
+}
+
+/// Identity subtable whose memory-checking fingerprint is the Lasso paper's
+/// `a * gamma^2 + v * gamma + t - tau` if `PAPER_FINGERPRINT`, or the default otherwise.
+enum IdentityStrategy<const PAPER_FINGERPRINT: bool> {}
+
+impl<const C: usize, const M: usize, const PAPER_FINGERPRINT: bool> SubtableStrategy<Fr, C, M>
+  for IdentityStrategy<PAPER_FINGERPRINT>
+{
+  const NUM_SUBTABLES: usize = 1;
+  const NUM_MEMORIES: usize = C;
+
+  fn materialize_subtables() -> [Vec<Fr>; <Self as SubtableStrategy<Fr, C, M>>::NUM_SUBTABLES] {
+    [(0..M).map(|i| Fr::from(i as u64)).collect()]
+  }
+
+  fn evaluate_subtable_mle(_: usize, point: &[Fr]) -> Fr {
+    let b = point.len();
+    let mut result = Fr::from(0u64);
+    for i in 0..b {
+      result += Fr::from(1u64 << i) * point[b - i - 1];
+    }
+    result
+  }
+
+  fn combine_lookups(vals: &[Fr; <Self as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES]) -> Fr {
+    let log_m = M.log_2();
+    let mut sum = Fr::from(0u64);
+    for (i, val) in vals.iter().enumerate() {
+      sum += Fr::from(1u64 << (i * log_m)) * val;
+    }
+    sum
+  }
+
+  fn g_poly_degree() -> usize {
+    1
+  }
+
+  fn fingerprint(a: &Fr, v: &Fr, t: &Fr, r_mem_check: &(Fr, Fr)) -> Fr {
+    use ark_ff::Field;
+
+    if PAPER_FINGERPRINT {
+      let (gamma, tau) = r_mem_check;
+      *a * gamma.square() + *v * gamma + t - tau
+    } else {
+      crate::lasso::memory_checking::reed_solomon_fingerprint(a, v, t, r_mem_check)
+    }
+  }
+}
+
+#[test]
+fn custom_fingerprint() {
+  use crate::utils::errors::ProofVerifyError;
+  use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+  type PaperProof = SparsePolynomialEvaluationProof<G1Projective, C, M, IdentityStrategy<true>>;
+  type DefaultProof = SparsePolynomialEvaluationProof<G1Projective, C, M, IdentityStrategy<false>>;
+
+  let (mut dense, gens, r) = instance::<IdentityStrategy<true>>();
+  let (proof, commitment) =
+    PaperProof::prove_and_verify(&mut dense, &r, &gens, b"example").unwrap();
+
+  // The same proof checked with the default fingerprint disagrees on the first memory's hashes
+  let mut bytes = Vec::new();
+  proof.serialize_compressed(&mut bytes).unwrap();
+  let proof = DefaultProof::deserialize_compressed(bytes.as_slice()).unwrap();
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify(&commitment, &r, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::HashLayerFailed(0, "init"))
+  ));
+}
+
+#[test]
+fn corrupted_grand_product_layer() {
+  use crate::utils::errors::ProofVerifyError;
+
+  let (mut proof, commitment, r, gens, _) = lt_fixture();
+  proof.memory_check.proof_prod_layer.proof_ops.proof[1].claims_prod_left[0] += Fr::from(1u64);
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  assert!(matches!(
+    proof.verify(&commitment, &r, &gens, &mut verifier_transcript),
+    Err(ProofVerifyError::GrandProductLayerFailed(1))
+  ));
+}
+
+#[test]
+fn memory_state_claim() {
+  type Strategy = LTSubtableStrategy;
+
+  let (proof, commitment, r, gens, dense) = lt_fixture();
+  let num_memories = <Strategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+
+  let mut verifier_transcript = Transcript::new(b"example");
+  let claim = proof
+    .verify_with_memory_state(&commitment, &r, &gens, &mut verifier_transcript)
+    .unwrap();
+  assert_eq!(claim.hashes_init.len(), num_memories);
+  assert_eq!(claim.hashes_final.len(), num_memories);
+
+  let subtables = <Strategy as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+  for i in 0..num_memories {
+    let subtable =
+      &subtables[<Strategy as SubtableStrategy<Fr, C, M>>::memory_to_subtable_index(i)];
+    let dim = <Strategy as SubtableStrategy<Fr, C, M>>::memory_to_dimension_index(i);
+    let mut final_counters = vec![0usize; M];
+    for address in dense.dim_usize[dim].iter() {
+      final_counters[*address] += 1;
+    }
+
+    let fingerprint = <Strategy as SubtableStrategy<Fr, C, M>>::fingerprint;
+    assert!(claim.verify_init(i, subtable, fingerprint));
+    assert!(claim.verify_final(i, subtable, &final_counters, fingerprint));
+    final_counters[0] += 1;
+    assert!(!claim.verify_final(i, subtable, &final_counters, fingerprint));
+  }
+
+  // Malformed inputs are rejected rather than panicking
+  let fingerprint = <Strategy as SubtableStrategy<Fr, C, M>>::fingerprint;
+  let subtable = &subtables[0];
+  let final_counters = vec![0usize; M];
+  assert!(!claim.verify_init(num_memories, subtable, fingerprint));
+  assert!(!claim.verify_init(0, &subtable[1..], fingerprint));
+  assert!(!claim.verify_final(num_memories, subtable, &final_counters, fingerprint));
+  assert!(!claim.verify_final(0, subtable, &final_counters[1..], fingerprint));
+  assert!(!claim.verify_final(0, &subtable[1..], &final_counters[1..], fingerprint));
+}
@@ -5,6 +5,15 @@ use super::surge::{SparsePolyCommitmentGens, SparsePolynomialCommitment};
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::utils::math::Math;
 
+/// Number of lookups after padding to a power of two, which fixes the size of every log(s)-variate
+/// polynomial. Shared by densification, generator setup, and verification so they always agree.
+///
+/// At least two lookups are always used so that the primary sumcheck has a round to prove and
+/// each grand product circuit has a layer to prove.
+pub fn padded_sparsity(num_lookups: usize) -> usize {
+  num_lookups.next_power_of_two().max(2)
+}
+
 pub struct DensifiedRepresentation<F: PrimeField, const C: usize> {
   pub dim_usize: [Vec<usize>; C],
   pub dim: [DensePolynomial<F>; C],
@@ -20,7 +29,7 @@ pub struct DensifiedRepresentation<F: PrimeField, const C: usize> {
 impl<F: PrimeField, const C: usize> DensifiedRepresentation<F, C> {
   #[tracing::instrument(skip_all, name = "Densify")]
   pub fn from_lookup_indices(indices: &Vec<[usize; C]>, log_m: usize) -> Self {
-    let s = indices.len().next_power_of_two();
+    let s = padded_sparsity(indices.len());
     let m = log_m.pow2();
 
     let mut dim_usize: Vec<Vec<usize>> = Vec::with_capacity(C);
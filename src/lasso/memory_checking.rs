@@ -1,6 +1,6 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::type_complexity)]
-use crate::lasso::densified::DensifiedRepresentation;
+use crate::lasso::densified::{padded_sparsity, DensifiedRepresentation};
 use crate::lasso::surge::{SparsePolyCommitmentGens, SparsePolynomialCommitment};
 use crate::poly::dense_mlpoly::{DensePolynomial, PolyEvalProof};
 use crate::poly::identity_poly::IdentityPolynomial;
@@ -8,10 +8,10 @@ use crate::subprotocols::grand_product::{BatchedGrandProductArgument, GrandProdu
 use crate::subtables::{
   CombinedTableCommitment, CombinedTableEvalProof, SubtableStrategy, Subtables,
 };
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{Cancelled, ProofVerifyError};
 use crate::utils::math::Math;
 use crate::utils::random::RandomTape;
-use crate::utils::transcript::ProofTranscript;
+use crate::utils::transcript::{ProofTranscript, ProtocolDomain};
 
 use ark_ec::CurveGroup;
 use ark_ff::{Field, PrimeField};
@@ -19,6 +19,7 @@ use ark_serialize::*;
 use ark_std::{One, Zero};
 use merlin::Transcript;
 use std::marker::Sync;
+use std::sync::atomic::AtomicBool;
 
 #[cfg(feature = "multicore")]
 use rayon::prelude::*;
@@ -32,7 +33,7 @@ pub struct MemoryCheckingProof<
 > where
   [(); S::NUM_MEMORIES]: Sized,
 {
-  proof_prod_layer: ProductLayerProof<G::ScalarField, { S::NUM_MEMORIES }>,
+  pub(crate) proof_prod_layer: ProductLayerProof<G::ScalarField, { S::NUM_MEMORIES }>,
   proof_hash_layer: HashLayerProof<G, C, M, S>,
 }
 
@@ -52,6 +53,7 @@ where
   /// - `gens`: Generates public parameters for polynomial commitments.
   /// - `transcript`: The proof transcript, used for Fiat-Shamir.
   /// - `random_tape`: Randomness for dense polynomial commitments.
+  /// - `cancel`: Checked between the grand product layers and before the hash layer.
   #[tracing::instrument(skip_all, name = "MemoryChecking.prove")]
   pub fn prove(
     dense: &DensifiedRepresentation<G::ScalarField, C>,
@@ -60,13 +62,15 @@ where
     gens: &SparsePolyCommitmentGens<G>,
     transcript: &mut Transcript,
     random_tape: &mut RandomTape<G>,
-  ) -> Self {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    cancel: &AtomicBool,
+  ) -> Result<Self, Cancelled> {
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
 
     let mut grand_products = subtables.to_grand_products(dense, r_mem_check);
     let (proof_prod_layer, rand_mem, rand_ops) =
-      ProductLayerProof::prove::<G>(&mut grand_products, transcript);
+      ProductLayerProof::prove::<G>(&mut grand_products, transcript, cancel)?;
 
+    Cancelled::check(cancel)?;
     let proof_hash_layer = HashLayerProof::prove(
       (&rand_mem, &rand_ops),
       dense,
@@ -76,10 +80,10 @@ where
       random_tape,
     );
 
-    MemoryCheckingProof {
+    Ok(MemoryCheckingProof {
       proof_prod_layer,
       proof_hash_layer,
-    }
+    })
   }
 
   /// Verifies that E_i polynomials are well-formed, i.e., that E_i(j) equals T_i[dim_i(j)] for all j ∈ {0, 1}^{log(m)},
@@ -102,11 +106,11 @@ where
     s: usize,
     transcript: &mut Transcript,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
 
     let (r_hash, r_multiset_check) = r_mem_check;
 
-    let num_ops = s.next_power_of_two();
+    let num_ops = padded_sparsity(s);
     let num_cells = comm.m;
 
     let (claims_mem, rand_mem, claims_ops, rand_ops) = self
@@ -119,11 +123,12 @@ where
       G::ScalarField,
       G::ScalarField,
     ); S::NUM_MEMORIES] = std::array::from_fn(|i| {
+      let (first, second) = batch_indices(i);
       (
-        claims_mem[2 * i],     // init
-        claims_ops[2 * i],     // read
-        claims_ops[2 * i + 1], // write
-        claims_mem[2 * i + 1], // final
+        claims_mem[first],  // init
+        claims_ops[first],  // read
+        claims_ops[second], // write
+        claims_mem[second], // final
       )
     });
 
@@ -142,11 +147,119 @@ where
     Ok(())
   }
 
-  fn protocol_name() -> &'static [u8] {
-    b"Lasso MemoryCheckingProof"
+  /// The fingerprint randomness and per-memory init/final multiset hashes claimed by this proof.
+  /// Only meaningful once `verify` has accepted the proof with the same `r_mem_check`.
+  pub(crate) fn memory_state_claim(
+    &self,
+    r_mem_check: &(G::ScalarField, G::ScalarField),
+  ) -> MemoryStateClaim<G::ScalarField> {
+    let (hashes_init, hashes_final) = (0..S::NUM_MEMORIES)
+      .map(|i| {
+        let (hash_init, _, _, hash_final) = self.proof_prod_layer.grand_product_evals(i);
+        (hash_init, hash_final)
+      })
+      .unzip();
+    MemoryStateClaim {
+      gamma: r_mem_check.0,
+      tau: r_mem_check.1,
+      memory_size: M,
+      hashes_init,
+      hashes_final,
+    }
+  }
+
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::MemoryChecking
+  }
+}
+
+/// Init and final multiset hashes of every memory in a verified memory-checking proof, together
+/// with the fingerprint randomness (gamma, tau) that defines them. Lets external protocols take
+/// the memory state as public input, e.g. to continue it in a different proof system.
+///
+/// hashes_init[i] = prod_a fingerprint(a, T_i[a], 0), and
+/// hashes_final[i] = prod_a fingerprint(a, T_i[a], final_i[a]),
+/// where T_i is the subtable backing memory i.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MemoryStateClaim<F: PrimeField> {
+  pub gamma: F,
+  pub tau: F,
+  /// Number of cells M of every memory, which the hashes range over.
+  pub memory_size: usize,
+  pub hashes_init: Vec<F>,
+  pub hashes_final: Vec<F>,
+}
+
+impl<F: PrimeField> MemoryStateClaim<F> {
+  /// Checks the claimed init hash of `memory_index` against its public subtable. Returns false,
+  /// rather than panicking, for an unknown memory or a subtable that is not M cells long.
+  pub fn verify_init(
+    &self,
+    memory_index: usize,
+    subtable: &[F],
+    fingerprint: fn(&F, &F, &F, &(F, F)) -> F,
+  ) -> bool {
+    if memory_index >= self.hashes_init.len() || subtable.len() != self.memory_size {
+      return false;
+    }
+    let hash_init = subtable
+      .iter()
+      .enumerate()
+      .map(|(a, v)| fingerprint(&F::from(a as u64), v, &F::zero(), &(self.gamma, self.tau)))
+      .product::<F>();
+    self.hashes_init[memory_index] == hash_init
+  }
+
+  /// Checks the claimed final hash of `memory_index` against its subtable and final counters.
+  /// Returns false, rather than panicking, for an unknown memory or inputs that are not M cells
+  /// long.
+  pub fn verify_final(
+    &self,
+    memory_index: usize,
+    subtable: &[F],
+    final_counters: &[usize],
+    fingerprint: fn(&F, &F, &F, &(F, F)) -> F,
+  ) -> bool {
+    if memory_index >= self.hashes_final.len()
+      || subtable.len() != self.memory_size
+      || final_counters.len() != self.memory_size
+    {
+      return false;
+    }
+    let hash_final = subtable
+      .iter()
+      .zip(final_counters.iter())
+      .enumerate()
+      .map(|(a, (v, t))| {
+        fingerprint(
+          &F::from(a as u64),
+          v,
+          &F::from(*t as u64),
+          &(self.gamma, self.tau),
+        )
+      })
+      .product::<F>();
+    self.hashes_final[memory_index] == hash_final
   }
 }
 
+/// Batching layout of the grand products, shared by prover and verifier: memory `i`'s pair of
+/// circuits (read/write in `proof_ops`, init/final in `proof_mem`) occupies positions `2i` and
+/// `2i + 1` of its batch.
+fn batch_indices(memory_index: usize) -> (usize, usize) {
+  (2 * memory_index, 2 * memory_index + 1)
+}
+
+/// Computes the Reed-Solomon fingerprint of the memory tuple (a, v, t):
+/// hash(a, v, t) = t * gamma^2 + v * gamma + a - tau
+///
+/// Note: this differs from the Lasso paper a little:
+/// (t * gamma^2 + v * gamma + a) instead of (a * gamma^2 + v * gamma + t)
+pub fn reed_solomon_fingerprint<F: PrimeField>(a: &F, v: &F, t: &F, r_mem_check: &(F, F)) -> F {
+  let (gamma, tau) = r_mem_check;
+  *t * gamma.square() + *v * *gamma + *a - tau
+}
+
 /// Contains grand product circuits to evaluate multi-set checks on memories.
 /// Evaluating each circuit is equivalent to computing the hash/fingerprint
 /// H_{\tau, \gamma} of the corresponding set.
@@ -172,6 +285,7 @@ impl<F: PrimeField> GrandProducts<F> {
   /// - `read_i`: "Counter polynomial" for memory reads.
   /// - `final_i` "Counter polynomial" for the final memory state.
   /// - `r_mem_check`: (gamma, tau) – Parameters for Reed-Solomon fingerprinting.
+  /// - `fingerprint`: Fingerprint of a memory tuple (a, v, t), see `SubtableStrategy::fingerprint`.
   pub fn new(
     eval_table: &[F],
     dim_i: &DensePolynomial<F>,
@@ -179,6 +293,7 @@ impl<F: PrimeField> GrandProducts<F> {
     read_i: &DensePolynomial<F>,
     final_i: &DensePolynomial<F>,
     r_mem_check: &(F, F),
+    fingerprint: fn(&F, &F, &F, &(F, F)) -> F,
   ) -> Self {
     let (
       grand_product_input_init,
@@ -192,6 +307,7 @@ impl<F: PrimeField> GrandProducts<F> {
       read_i,
       final_i,
       r_mem_check,
+      fingerprint,
     );
 
     let prod_init = GrandProductCircuit::new(&grand_product_input_init);
@@ -228,6 +344,7 @@ impl<F: PrimeField> GrandProducts<F> {
   /// - `read_i`: "Counter polynomial" for memory reads.
   /// - `final_i` "Counter polynomial" for the final memory state.
   /// - `r_mem_check`: (gamma, tau) – Parameters for Reed-Solomon fingerprinting (see `hash_func` closure).
+  /// - `fingerprint`: Fingerprint of a memory tuple (a, v, t), see `SubtableStrategy::fingerprint`.
   ///
   /// Returns
   /// - `(init, read, write, final)`: These are the memory polynomials as described in the Spartan paper.
@@ -240,16 +357,14 @@ impl<F: PrimeField> GrandProducts<F> {
     read_i: &DensePolynomial<F>,
     final_i: &DensePolynomial<F>,
     r_mem_check: &(F, F),
+    fingerprint: fn(&F, &F, &F, &(F, F)) -> F,
   ) -> (
     DensePolynomial<F>,
     DensePolynomial<F>,
     DensePolynomial<F>,
     DensePolynomial<F>,
   ) {
-    let (gamma, tau) = r_mem_check;
-
-    // hash(a, v, t) = t * gamma^2 + v * gamma + a - tau
-    let hash_func = |a: &F, v: &F, t: &F| -> F { *t * gamma.square() + *v * *gamma + *a - tau };
+    let hash_func = |a: &F, v: &F, t: &F| -> F { fingerprint(a, v, t, r_mem_check) };
 
     // init: M hash evaluations => log(M)-variate polynomial
     assert_eq!(eval_table.len(), final_i.len());
@@ -310,6 +425,12 @@ impl<F: PrimeField> GrandProducts<F> {
   }
 }
 
+// Transcript instances of the hash layer's openings. Instance 0 is the primary sumcheck's opening
+// of the E_i, see `SparsePolynomialEvaluationProof`.
+const HASH_LAYER_DEREFS_OPENING: u64 = 1;
+const HASH_LAYER_OPS_OPENING: u64 = 2;
+const HASH_LAYER_MEM_OPENING: u64 = 3;
+
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 struct HashLayerProof<
   G: CurveGroup,
@@ -343,29 +464,41 @@ where
     transcript: &mut Transcript,
     random_tape: &mut RandomTape<G>,
   ) -> Self {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
 
     let (rand_mem, rand_ops) = rand;
 
+    // E_i, dim_i, and read_i are all opened at rand_ops, so evaluate them together
+    let polys_ops: Vec<&DensePolynomial<G::ScalarField>> = subtables
+      .lookup_polys
+      .iter()
+      .chain(dense.dim.iter())
+      .chain(dense.read.iter())
+      .collect();
+    let evals_at_rand_ops = DensePolynomial::batch_evaluate(&polys_ops, rand_ops);
+    let (evals_derefs, evals_dim_read) = evals_at_rand_ops.split_at(S::NUM_MEMORIES);
+
     // decommit derefs at rand_ops
-    let eval_derefs: [G::ScalarField; S::NUM_MEMORIES] =
-      std::array::from_fn(|i| subtables.lookup_polys[i].evaluate(rand_ops));
+    let eval_derefs: [G::ScalarField; S::NUM_MEMORIES] = evals_derefs.try_into().unwrap();
     let proof_derefs = CombinedTableEvalProof::prove(
       &subtables.combined_poly,
       eval_derefs.as_ref(),
       rand_ops,
       &gens.gens_derefs,
       transcript,
+      HASH_LAYER_DEREFS_OPENING,
       random_tape,
     );
 
     // form a single decommitment using comm_comb_ops
     let mut evals_ops: Vec<G::ScalarField> = Vec::new(); // moodlezoup: changed order of evals_ops
 
-    let eval_dim: [G::ScalarField; C] = std::array::from_fn(|i| dense.dim[i].evaluate(rand_ops));
-    let eval_read: [G::ScalarField; C] = std::array::from_fn(|i| dense.read[i].evaluate(rand_ops));
+    let eval_dim: [G::ScalarField; C] = evals_dim_read[..C].try_into().unwrap();
+    let eval_read: [G::ScalarField; C] = evals_dim_read[C..].try_into().unwrap();
     let eval_final: [G::ScalarField; C] =
-      std::array::from_fn(|i| dense.r#final[i].evaluate(rand_mem));
+      DensePolynomial::batch_evaluate(&dense.r#final.iter().collect::<Vec<_>>(), rand_mem)
+        .try_into()
+        .unwrap();
 
     evals_ops.extend(eval_dim);
     evals_ops.extend(eval_read);
@@ -407,6 +540,7 @@ where
       None,
       &gens.gens_combined_l_variate,
       transcript,
+      HASH_LAYER_OPS_OPENING,
       random_tape,
     );
 
@@ -445,6 +579,7 @@ where
       None,
       &gens.gens_combined_log_m_variate,
       transcript,
+      HASH_LAYER_MEM_OPENING,
       random_tape,
     );
 
@@ -463,6 +598,7 @@ where
   /// are as claimed by the final sumchecks of their respective grand product arguments.
   ///
   /// Params
+  /// - `memory_index`: Which memory the claims belong to, reported on failure.
   /// - `claims`: Fingerprint values of the init, read, write, and final multisets, as
   /// as claimed by their respective grand product arguments.
   /// - `eval_deref`: The evaluation E_i(r'''_i).
@@ -475,6 +611,7 @@ where
   /// - `gamma`: Random value used to compute the Reed-Solomon fingerprint.
   /// - `tau`: Random value used to compute the Reed-Solomon fingerprint.
   fn check_reed_solomon_fingerprints(
+    memory_index: usize,
     claims: &(
       G::ScalarField,
       G::ScalarField,
@@ -490,34 +627,45 @@ where
     gamma: &G::ScalarField,
     tau: &G::ScalarField,
   ) -> Result<(), ProofVerifyError> {
-    // Computes the Reed-Solomon fingerprint of the tuple (a, v, t)
+    // Computes the fingerprint of the tuple (a, v, t)
+    let r_mem_check = (*gamma, *tau);
     let hash_func = |a: &G::ScalarField,
                      v: &G::ScalarField,
                      t: &G::ScalarField|
-     -> G::ScalarField { *t * gamma.square() + *v * *gamma + *a - tau };
-    // Note: this differs from the Lasso paper a little:
-    // (t * gamma^2 + v * gamma + a) instead of (a * gamma^2 + v * gamma + t)
+     -> G::ScalarField { S::fingerprint(a, v, t, &r_mem_check) };
 
     let (claim_init, claim_read, claim_write, claim_final) = claims;
 
     // init
     let hash_init = hash_func(init_addr, init_memory, &G::ScalarField::zero());
-    assert_eq!(&hash_init, claim_init); // verify the last claim of the `init` grand product sumcheck
+    // verify the last claim of the `init` grand product sumcheck
+    if &hash_init != claim_init {
+      return Err(ProofVerifyError::HashLayerFailed(memory_index, "init"));
+    }
 
     // read
     let hash_read = hash_func(eval_dim, eval_deref, eval_read);
-    assert_eq!(hash_read, *claim_read); // verify the last claim of the `read` grand product sumcheck
+    // verify the last claim of the `read` grand product sumcheck
+    if hash_read != *claim_read {
+      return Err(ProofVerifyError::HashLayerFailed(memory_index, "read"));
+    }
 
     // write: shares addr, val with read
     let eval_write = *eval_read + G::ScalarField::one();
     let hash_write = hash_func(eval_dim, eval_deref, &eval_write);
-    assert_eq!(hash_write, *claim_write); // verify the last claim of the `write` grand product sumcheck
+    // verify the last claim of the `write` grand product sumcheck
+    if hash_write != *claim_write {
+      return Err(ProofVerifyError::HashLayerFailed(memory_index, "write"));
+    }
 
     // final: shares addr and val with init
     let eval_final_addr = init_addr;
     let eval_final_val = init_memory;
     let hash_final = hash_func(eval_final_addr, eval_final_val, eval_final);
-    assert_eq!(hash_final, *claim_final); // verify the last claim of the `final` grand product sumcheck
+    // verify the last claim of the `final` grand product sumcheck
+    if hash_final != *claim_final {
+      return Err(ProofVerifyError::HashLayerFailed(memory_index, "final"));
+    }
 
     Ok(())
   }
@@ -538,7 +686,7 @@ where
     r_multiset_check: &G::ScalarField,
     transcript: &mut Transcript,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
 
     let (rand_mem, rand_ops) = rand;
 
@@ -550,6 +698,7 @@ where
       &gens.gens_derefs,
       table_eval_commitment,
       transcript,
+      HASH_LAYER_DEREFS_OPENING,
     )?;
 
     let mut evals_ops: Vec<G::ScalarField> = Vec::new();
@@ -585,6 +734,7 @@ where
     self.proof_ops.verify_plain(
       &gens.gens_combined_l_variate,
       transcript,
+      HASH_LAYER_OPS_OPENING,
       &r_joint_ops,
       &joint_claim_eval_ops,
       &comm.l_variate_polys_commitment,
@@ -620,6 +770,7 @@ where
     self.proof_mem.verify_plain(
       &gens.gens_combined_log_m_variate,
       transcript,
+      HASH_LAYER_MEM_OPENING,
       &r_joint_mem,
       &joint_claim_eval_mem,
       &comm.log_m_variate_polys_commitment,
@@ -633,6 +784,7 @@ where
       // Check ALPHA memories / lookup polys / grand products
       // Only need 'C' indices / dimensions / read_timestamps / final_timestamps
       Self::check_reed_solomon_fingerprints(
+        i,
         grand_product_claim,
         &self.eval_derefs[i],
         &self.eval_dim[j],
@@ -647,21 +799,49 @@ where
     Ok(())
   }
 
-  fn protocol_name() -> &'static [u8] {
-    b"Lasso HashLayerProof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::HashLayer
   }
 }
 
+// Transcript instances of the two batched grand products
+const OPS_GRAND_PRODUCT: u64 = 0;
+const MEM_GRAND_PRODUCT: u64 = 1;
+
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
-struct ProductLayerProof<F: PrimeField, const NUM_MEMORIES: usize> {
-  grand_product_evals: [(F, F, F, F); NUM_MEMORIES],
+pub(crate) struct ProductLayerProof<F: PrimeField, const NUM_MEMORIES: usize> {
+  /// Claimed read and write hashes, laid out as batched in `proof_ops` (see `batch_indices`).
+  hashes_ops: Vec<F>,
+  /// Claimed init and final hashes, laid out as batched in `proof_mem` (see `batch_indices`).
+  hashes_mem: Vec<F>,
   proof_mem: BatchedGrandProductArgument<F>,
-  proof_ops: BatchedGrandProductArgument<F>,
+  pub(crate) proof_ops: BatchedGrandProductArgument<F>,
 }
 
 impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES> {
-  fn protocol_name() -> &'static [u8] {
-    b"Lasso ProductLayerProof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::ProductLayer
+  }
+
+  /// The claimed (init, read, write, final) hashes of memory `memory_index`.
+  fn grand_product_evals(&self, memory_index: usize) -> (F, F, F, F) {
+    let (first, second) = batch_indices(memory_index);
+    (
+      self.hashes_mem[first],
+      self.hashes_ops[first],
+      self.hashes_ops[second],
+      self.hashes_mem[second],
+    )
+  }
+
+  /// Appends the claimed hashes of every memory to the transcript, one batched message per
+  /// grand product argument rather than one message per (memory, multiset) pair.
+  fn append_grand_product_evals<G>(hashes_ops: &[F], hashes_mem: &[F], transcript: &mut Transcript)
+  where
+    G: CurveGroup<ScalarField = F>,
+  {
+    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"claim_hashes_ops", hashes_ops);
+    <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"claim_hashes_mem", hashes_mem);
   }
 
   /// Performs grand product argument proofs required for memory-checking.
@@ -670,64 +850,74 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
   /// Params
   /// - `grand_products`: The grand product circuits whose evaluations are proven.
   /// - `transcript`: The proof transcript, used for Fiat-Shamir.
+  /// - `cancel`: Checked between the grand product layers.
   #[tracing::instrument(skip_all, name = "ProductLayer.prove")]
   pub fn prove<G>(
     grand_products: &mut Vec<GrandProducts<F>>,
     transcript: &mut Transcript,
-  ) -> (Self, Vec<F>, Vec<F>)
+    cancel: &AtomicBool,
+  ) -> Result<(Self, Vec<F>, Vec<F>), Cancelled>
   where
     G: CurveGroup<ScalarField = F>,
   {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
-
-    let grand_product_evals: [(F, F, F, F); NUM_MEMORIES] = std::array::from_fn(|i| {
-      let hash_init = grand_products[i].init.evaluate();
-      let hash_read = grand_products[i].read.evaluate();
-      let hash_write = grand_products[i].write.evaluate();
-      let hash_final = grand_products[i].r#final.evaluate();
-
-      assert_eq!(hash_init * hash_write, hash_read * hash_final);
-
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_init", &hash_init);
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_read", &hash_read);
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_write",
-        &hash_write,
-      );
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_final",
-        &hash_final,
-      );
-
-      (hash_init, hash_read, hash_write, hash_final)
-    });
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
+
+    let mut read_write_grand_products: Vec<&mut GrandProductCircuit<F>> =
+      Vec::with_capacity(2 * NUM_MEMORIES);
+    let mut init_final_grand_products: Vec<&mut GrandProductCircuit<F>> =
+      Vec::with_capacity(2 * NUM_MEMORIES);
+    for (i, grand_product) in grand_products.iter_mut().enumerate() {
+      let GrandProducts {
+        init,
+        read,
+        write,
+        r#final,
+      } = grand_product;
+      debug_assert_eq!(batch_indices(i).0, read_write_grand_products.len());
+      read_write_grand_products.extend([read, write]);
+      init_final_grand_products.extend([init, r#final]);
+    }
 
-    let mut read_write_grand_products: Vec<&mut GrandProductCircuit<F>> = grand_products
-      .iter_mut()
-      .flat_map(|grand_product| [&mut grand_product.read, &mut grand_product.write])
+    let hashes_ops: Vec<F> = read_write_grand_products
+      .iter()
+      .map(|circuit| circuit.evaluate())
       .collect();
-
-    let (proof_ops, rand_ops) =
-      BatchedGrandProductArgument::<F>::prove::<G>(&mut read_write_grand_products, transcript);
-
-    let mut init_final_grand_products: Vec<&mut GrandProductCircuit<F>> = grand_products
-      .iter_mut()
-      .flat_map(|grand_product| [&mut grand_product.init, &mut grand_product.r#final])
+    let hashes_mem: Vec<F> = init_final_grand_products
+      .iter()
+      .map(|circuit| circuit.evaluate())
       .collect();
+    for i in 0..NUM_MEMORIES {
+      let (first, second) = batch_indices(i);
+      assert_eq!(
+        hashes_mem[first] * hashes_ops[second],
+        hashes_ops[first] * hashes_mem[second]
+      );
+    }
+    Self::append_grand_product_evals::<G>(&hashes_ops, &hashes_mem, transcript);
+
+    let (proof_ops, rand_ops) = BatchedGrandProductArgument::<F>::prove::<G>(
+      &mut read_write_grand_products,
+      transcript,
+      OPS_GRAND_PRODUCT,
+      cancel,
+    )?;
 
     // produce a batched proof of memory-related product circuits
-    let (proof_mem, rand_mem) =
-      BatchedGrandProductArgument::<F>::prove::<G>(&mut init_final_grand_products, transcript);
+    let (proof_mem, rand_mem) = BatchedGrandProductArgument::<F>::prove::<G>(
+      &mut init_final_grand_products,
+      transcript,
+      MEM_GRAND_PRODUCT,
+      cancel,
+    )?;
 
     let product_layer_proof = ProductLayerProof {
-      grand_product_evals,
+      hashes_ops,
+      hashes_mem,
       proof_mem,
       proof_ops,
     };
 
-    (product_layer_proof, rand_mem, rand_ops)
+    Ok((product_layer_proof, rand_mem, rand_ops))
   }
 
   pub fn verify<G>(
@@ -739,47 +929,38 @@ impl<F: PrimeField, const NUM_MEMORIES: usize> ProductLayerProof<F, NUM_MEMORIES
   where
     G: CurveGroup<ScalarField = F>,
   {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
-
-    for (hash_init, hash_read, hash_write, hash_final) in self.grand_product_evals {
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
+
+    for hashes in [&self.hashes_ops, &self.hashes_mem] {
+      if hashes.len() != 2 * NUM_MEMORIES {
+        return Err(ProofVerifyError::InvalidInputLength(
+          2 * NUM_MEMORIES,
+          hashes.len(),
+        ));
+      }
+    }
+    for i in 0..NUM_MEMORIES {
+      let (hash_init, hash_read, hash_write, hash_final) = self.grand_product_evals(i);
       // Multiset equality check
-      assert_eq!(hash_init * hash_write, hash_read * hash_final);
-
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_init", &hash_init);
-      <Transcript as ProofTranscript<G>>::append_scalar(transcript, b"claim_hash_read", &hash_read);
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_write",
-        &hash_write,
-      );
-      <Transcript as ProofTranscript<G>>::append_scalar(
-        transcript,
-        b"claim_hash_final",
-        &hash_final,
-      );
+      if hash_init * hash_write != hash_read * hash_final {
+        return Err(ProofVerifyError::ProductLayerFailed(i));
+      }
     }
+    Self::append_grand_product_evals::<G>(&self.hashes_ops, &self.hashes_mem, transcript);
 
-    let read_write_claims: Vec<F> = self
-      .grand_product_evals
-      .iter()
-      .flat_map(|(_, hash_read, hash_write, _)| [*hash_read, *hash_write])
-      .collect();
-
-    let (claims_ops, rand_ops) =
-      self
-        .proof_ops
-        .verify::<G, Transcript>(&read_write_claims, num_ops, transcript);
-
-    let init_final_claims: Vec<F> = self
-      .grand_product_evals
-      .iter()
-      .flat_map(|(hash_init, _, _, hash_final)| [*hash_init, *hash_final])
-      .collect();
+    let (claims_ops, rand_ops) = self.proof_ops.verify::<G, Transcript>(
+      &self.hashes_ops,
+      num_ops,
+      transcript,
+      OPS_GRAND_PRODUCT,
+    )?;
 
-    let (claims_mem, rand_mem) =
-      self
-        .proof_mem
-        .verify::<G, Transcript>(&init_final_claims, num_cells, transcript);
+    let (claims_mem, rand_mem) = self.proof_mem.verify::<G, Transcript>(
+      &self.hashes_mem,
+      num_cells,
+      transcript,
+      MEM_GRAND_PRODUCT,
+    )?;
 
     Ok((claims_mem, rand_mem, claims_ops, rand_ops))
   }
@@ -827,6 +1008,91 @@ mod test {
       &read_i,
       &final_i,
       &r_mem_check,
+      reed_solomon_fingerprint,
     );
   }
+
+  #[test]
+  fn batch_layout() {
+    use ark_curve25519::EdwardsProjective as G1Projective;
+
+    // Two memories over distinct tables, so that every (memory, multiset) hash differs
+    let tables: Vec<Vec<Fr>> = (0..2u64)
+      .map(|k| (0..8u64).map(|a| Fr::from(10 + 100 * k + a)).collect())
+      .collect();
+    let dim_i = DensePolynomial::new(vec![Fr::from(1), Fr::from(2), Fr::from(1), Fr::from(5)]);
+    let dim_i_usize = vec![1usize, 2, 1, 5];
+    let read_i = DensePolynomial::new(vec![Fr::from(0), Fr::from(0), Fr::from(1), Fr::from(0)]);
+    let final_i = DensePolynomial::new(
+      [0u64, 2, 1, 0, 0, 1, 0, 0]
+        .iter()
+        .map(|t| Fr::from(*t))
+        .collect(),
+    );
+    let r_mem_check = (Fr::from(100), Fr::from(200));
+
+    let leaves: Vec<_> = tables
+      .iter()
+      .map(|table| {
+        GrandProducts::build_grand_product_inputs(
+          table,
+          &dim_i,
+          &dim_i_usize,
+          &read_i,
+          &final_i,
+          &r_mem_check,
+          reed_solomon_fingerprint,
+        )
+      })
+      .collect();
+    let mut grand_products: Vec<GrandProducts<Fr>> = tables
+      .iter()
+      .map(|table| {
+        GrandProducts::new(
+          table,
+          &dim_i,
+          &dim_i_usize,
+          &read_i,
+          &final_i,
+          &r_mem_check,
+          reed_solomon_fingerprint,
+        )
+      })
+      .collect();
+
+    let mut prover_transcript = Transcript::new(b"test_transcript");
+    let (proof, rand_mem, rand_ops) = ProductLayerProof::<Fr, 2>::prove::<G1Projective>(
+      &mut grand_products,
+      &mut prover_transcript,
+      &AtomicBool::new(false),
+    )
+    .unwrap();
+
+    let mut verifier_transcript = Transcript::new(b"test_transcript");
+    let (claims_mem, verifier_rand_mem, claims_ops, verifier_rand_ops) = proof
+      .verify::<G1Projective>(4, 8, &mut verifier_transcript)
+      .unwrap();
+    assert_eq!(rand_mem, verifier_rand_mem);
+    assert_eq!(rand_ops, verifier_rand_ops);
+
+    // The batched arguments reduce memory i's hashes to the evaluations of its leaves at `rand`,
+    // in the positions given by `batch_indices(i)`
+    let product = |poly: &DensePolynomial<Fr>| (0..poly.len()).map(|k| poly[k]).product::<Fr>();
+    for (i, (init, read, write, r#final)) in leaves.iter().enumerate() {
+      let (first, second) = batch_indices(i);
+      assert_eq!(claims_mem[first], init.evaluate(&rand_mem));
+      assert_eq!(claims_ops[first], read.evaluate(&rand_ops));
+      assert_eq!(claims_ops[second], write.evaluate(&rand_ops));
+      assert_eq!(claims_mem[second], r#final.evaluate(&rand_mem));
+      assert_eq!(
+        proof.grand_product_evals(i),
+        (
+          product(init),
+          product(read),
+          product(write),
+          product(r#final)
+        )
+      );
+    }
+  }
 }
@@ -2,25 +2,31 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::needless_range_loop)]
 
-use crate::lasso::densified::DensifiedRepresentation;
-use crate::lasso::memory_checking::MemoryCheckingProof;
+use crate::lasso::densified::{padded_sparsity, DensifiedRepresentation};
+use crate::lasso::memory_checking::{MemoryCheckingProof, MemoryStateClaim};
 use crate::poly::dense_mlpoly::{DensePolynomial, PolyCommitment, PolyCommitmentGens};
 use crate::poly::eq_poly::EqPolynomial;
 use crate::subprotocols::sumcheck::SumcheckInstanceProof;
 use crate::subtables::{
   CombinedTableCommitment, CombinedTableEvalProof, SubtableStrategy, Subtables,
 };
-use crate::utils::errors::ProofVerifyError;
+use crate::utils::errors::{Cancelled, ProofVerifyError};
 use crate::utils::math::Math;
 use crate::utils::random::RandomTape;
-use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
+use crate::utils::transcript::{
+  transcript_digest, AppendToTranscript, ProofTranscript, ProtocolDomain,
+};
 use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
 
 use ark_serialize::*;
 
 use ark_std::log2;
 use merlin::Transcript;
 use std::marker::Sync;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 pub struct SparsePolyCommitmentGens<G> {
   pub gens_combined_l_variate: PolyCommitmentGens<G>,
@@ -36,6 +42,7 @@ impl<G: CurveGroup> SparsePolyCommitmentGens<G> {
     num_memories: usize,
     log_m: usize,
   ) -> SparsePolyCommitmentGens<G> {
+    let s = padded_sparsity(s);
     // dim_1, ... dim_c, read_1, ..., read_c
     // log_2(cs + cs)
     let num_vars_combined_l_variate = (2 * c * s).next_power_of_two().log_2();
@@ -81,6 +88,10 @@ impl<G: CurveGroup> AppendToTranscript<G> for SparsePolynomialCommitment<G> {
   }
 }
 
+// Transcript instance of the primary sumcheck and of its opening of the E_i. Memory checking
+// numbers its own sumchecks and openings so that they never reuse it.
+const PRIMARY_INSTANCE: u64 = 0;
+
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 struct PrimarySumcheck<G: CurveGroup, const ALPHA: usize> {
   proof: SumcheckInstanceProof<G::ScalarField>,
@@ -89,6 +100,36 @@ struct PrimarySumcheck<G: CurveGroup, const ALPHA: usize> {
   proof_derefs: CombinedTableEvalProof<G, ALPHA>,
 }
 
+/// Digests of the Fiat-Shamir transcript state at fixed checkpoints of a Surge proof, so that
+/// external statements can be bound to a specific proving session without replaying the
+/// verifier's transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct TranscriptDigests {
+  /// After the commitment to the E_i polynomials and the claimed evaluation.
+  pub post_commitments: [u8; 32],
+  /// After the primary sumcheck and the opening of the E_i polynomials at its random point.
+  pub post_sumcheck: [u8; 32],
+}
+
+/// Verifier cost breakdown reported by `verify_with_profiling`: wall-clock time per subprotocol
+/// and the proof size. Only timings are measured; operation counts (field operations, MSM sizes)
+/// are not tracked.
+///
+/// Transcript bytes are not reported: merlin's `Transcript` does not expose how much it has
+/// absorbed, and the verifier is written against the concrete `Transcript` rather than a
+/// `ProofTranscript` that could count them.
+#[derive(Debug, Clone, Default)]
+pub struct VerifierProfile {
+  /// Primary sumcheck, including absorbing the E_i commitment and the claimed evaluation.
+  pub primary_sumcheck: Duration,
+  /// Opening of the E_i polynomials at the primary sumcheck's random point.
+  pub derefs_opening: Duration,
+  /// Memory-checking (product layer and hash layer).
+  pub memory_checking: Duration,
+  /// Size of the compressed proof in bytes.
+  pub proof_size: usize,
+}
+
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SparsePolynomialEvaluationProof<
   G: CurveGroup,
@@ -100,7 +141,8 @@ pub struct SparsePolynomialEvaluationProof<
 {
   comm_derefs: CombinedTableCommitment<G>,
   primary_sumcheck: PrimarySumcheck<G, { S::NUM_MEMORIES }>,
-  memory_check: MemoryCheckingProof<G, C, M, S>,
+  pub(crate) memory_check: MemoryCheckingProof<G, C, M, S>,
+  pub(crate) transcript_digests: TranscriptDigests,
 }
 
 impl<G: CurveGroup, const C: usize, const M: usize, S: SubtableStrategy<G::ScalarField, C, M> + Sync>
@@ -115,7 +157,6 @@ where
   /// - `r`: log(s) sized coordinates at which to prove the evaluation of eq in the primary sumcheck
   /// - `eval`: evaluation of \widetilde{M}(r = (r_1, ..., r_logM))
   /// - `gens`: Commitment generator
-  #[tracing::instrument(skip_all, name = "SparsePoly.prove")]
   pub fn prove(
     dense: &mut DensifiedRepresentation<G::ScalarField, C>,
     r: &Vec<G::ScalarField>,
@@ -126,62 +167,162 @@ where
   where
     [(); S::NUM_SUBTABLES]: Sized,
   {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    Self::prove_with_subtables(
+      dense,
+      r,
+      gens,
+      transcript,
+      random_tape,
+      Arc::new(S::materialize_subtables()),
+    )
+  }
 
-    assert_eq!(r.len(), log2(dense.s) as usize);
+  /// Proves as `prove` does, with subtables produced by `S::materialize_subtables` that the caller
+  /// already holds, e.g. from `Subtables::shared_materialization`, so that concurrent provers share
+  /// one read-only copy.
+  pub fn prove_with_subtables(
+    dense: &mut DensifiedRepresentation<G::ScalarField, C>,
+    r: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+    random_tape: &mut RandomTape<G>,
+    materialized_subtables: Arc<[Vec<G::ScalarField>; S::NUM_SUBTABLES]>,
+  ) -> Self
+  where
+    [(); S::NUM_SUBTABLES]: Sized,
+  {
+    // The flag is never set, so proving always runs to completion
+    Self::prove_cancellable_with_subtables(
+      dense,
+      r,
+      gens,
+      transcript,
+      random_tape,
+      materialized_subtables,
+      &AtomicBool::new(false),
+    )
+    .unwrap()
+  }
+
+  /// Proves as `prove` does, but gives up with `Cancelled` once `cancel` is set, e.g. by a timeout
+  /// on another thread. The flag is checked after the commitments, between sumcheck rounds and
+  /// between grand product layers; the transcript and random tape are left mid-proof.
+  pub fn prove_cancellable(
+    dense: &mut DensifiedRepresentation<G::ScalarField, C>,
+    r: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+    random_tape: &mut RandomTape<G>,
+    cancel: &AtomicBool,
+  ) -> Result<Self, Cancelled>
+  where
+    [(); S::NUM_SUBTABLES]: Sized,
+  {
+    Self::prove_cancellable_with_subtables(
+      dense,
+      r,
+      gens,
+      transcript,
+      random_tape,
+      Arc::new(S::materialize_subtables()),
+      cancel,
+    )
+  }
 
-    let subtables = Subtables::<_, C, M, S>::new(&dense.dim_usize, dense.s);
+  #[tracing::instrument(
+    skip_all,
+    name = "SparsePoly.prove",
+    fields(s = dense.s, num_memories = S::NUM_MEMORIES)
+  )]
+  fn prove_cancellable_with_subtables(
+    dense: &mut DensifiedRepresentation<G::ScalarField, C>,
+    r: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+    random_tape: &mut RandomTape<G>,
+    materialized_subtables: Arc<[Vec<G::ScalarField>; S::NUM_SUBTABLES]>,
+    cancel: &AtomicBool,
+  ) -> Result<Self, Cancelled>
+  where
+    [(); S::NUM_SUBTABLES]: Sized,
+  {
+    Cancelled::check(cancel)?;
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
 
-    // commit to non-deterministic choices of the prover
-    let comm_derefs = {
-      let comm = subtables.commit(&gens.gens_derefs);
-      comm.append_to_transcript(b"comm_poly_row_col_ops_val", transcript);
-      comm
-    };
+    assert_eq!(r.len(), log2(dense.s) as usize);
+
+    let subtables =
+      Subtables::<_, C, M, S>::from_materialized(&dense.dim_usize, dense.s, materialized_subtables);
 
     let eq = EqPolynomial::new(r.clone());
-    let claimed_eval = subtables.compute_sumcheck_claim(&eq);
+
+    // Neither the commitment to the E_i (MSM-bound) nor the sumcheck claim and eq table
+    // (field-op bound) depend on the transcript, so they are computed concurrently.
+    #[cfg(feature = "multicore")]
+    let (comm_derefs, (claimed_eval, eq_evals)) = rayon::join(
+      || subtables.commit(&gens.gens_derefs),
+      || (subtables.compute_sumcheck_claim(&eq), eq.evals()),
+    );
+
+    #[cfg(not(feature = "multicore"))]
+    let (comm_derefs, (claimed_eval, eq_evals)) = (
+      subtables.commit(&gens.gens_derefs),
+      (subtables.compute_sumcheck_claim(&eq), eq.evals()),
+    );
+
+    // commit to non-deterministic choices of the prover
+    comm_derefs.append_to_transcript(b"comm_poly_row_col_ops_val", transcript);
 
     <Transcript as ProofTranscript<G>>::append_scalar(
       transcript,
       b"claim_eval_scalar_product",
       &claimed_eval,
     );
+    let post_commitments = transcript_digest(transcript, b"digest_post_commitments");
+    Cancelled::check(cancel)?;
 
+    let mut eq_poly = Some(DensePolynomial::new(eq_evals));
     let mut combined_sumcheck_polys: [DensePolynomial<G::ScalarField>; S::NUM_MEMORIES + 1] =
       std::array::from_fn(|i| {
         if i != S::NUM_MEMORIES {
           subtables.lookup_polys[i].clone()
         } else {
-          DensePolynomial::new(eq.evals())
+          eq_poly.take().unwrap()
         }
       });
 
-    let (primary_sumcheck_proof, r_z, _) = SumcheckInstanceProof::<G::ScalarField>::prove_arbitrary::<
-      _,
-      G,
-      Transcript,
-      { S::NUM_MEMORIES + 1 },
-    >(
-      &claimed_eval,
-      dense.s.log_2(),
-      &mut combined_sumcheck_polys,
-      S::combine_lookups_eq,
-      S::sumcheck_poly_degree(),
-      transcript,
-    );
+    let (primary_sumcheck_proof, r_z, _) =
+      SumcheckInstanceProof::<G::ScalarField>::prove_arbitrary::<
+        _,
+        G,
+        Transcript,
+        { S::NUM_MEMORIES + 1 },
+      >(
+        &claimed_eval,
+        dense.s.log_2(),
+        &mut combined_sumcheck_polys,
+        S::combine_lookups_eq,
+        S::sumcheck_poly_degree(),
+        transcript,
+        PRIMARY_INSTANCE,
+        cancel,
+      )?;
 
     // Combined eval proof for E_i(r_z)
     let eval_derefs: [G::ScalarField; S::NUM_MEMORIES] =
-      std::array::from_fn(|i| subtables.lookup_polys[i].evaluate(&r_z));
+      DensePolynomial::batch_evaluate(&subtables.lookup_polys.iter().collect::<Vec<_>>(), &r_z)
+        .try_into()
+        .unwrap();
     let proof_derefs = CombinedTableEvalProof::prove(
       &subtables.combined_poly,
       eval_derefs.as_ref(),
       &r_z,
       &gens.gens_derefs,
       transcript,
+      PRIMARY_INSTANCE,
       random_tape,
     );
+    let post_sumcheck = transcript_digest(transcript, b"digest_post_sumcheck");
 
     let memory_check = {
       // produce a random element from the transcript for hash function
@@ -195,10 +336,11 @@ where
         gens,
         transcript,
         random_tape,
-      )
+        cancel,
+      )?
     };
 
-    Self {
+    Ok(Self {
       comm_derefs,
       primary_sumcheck: PrimarySumcheck {
         proof: primary_sumcheck_proof,
@@ -207,7 +349,32 @@ where
         proof_derefs,
       },
       memory_check,
-    }
+      transcript_digests: TranscriptDigests {
+        post_commitments,
+        post_sumcheck,
+      },
+    })
+  }
+
+  /// Commits to `dense`, proves, and immediately verifies the proof against a fresh transcript
+  /// with the same label, returning the proof and commitment only if they verify. Saves the
+  /// transcript and random tape setup when developing and testing new subtable strategies.
+  pub fn prove_and_verify(
+    dense: &mut DensifiedRepresentation<G::ScalarField, C>,
+    r: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript_label: &'static [u8],
+  ) -> Result<(Self, SparsePolynomialCommitment<G>), ProofVerifyError> {
+    let commitment = dense.commit::<G>(gens);
+
+    let mut random_tape = RandomTape::new(b"proof");
+    let mut prover_transcript = Transcript::new(transcript_label);
+    let proof = Self::prove(dense, r, gens, &mut prover_transcript, &mut random_tape);
+
+    let mut verifier_transcript = Transcript::new(transcript_label);
+    proof.verify(&commitment, r, gens, &mut verifier_transcript)?;
+
+    Ok((proof, commitment))
   }
 
   #[tracing::instrument(skip_all, name = "SparsePoly.verify")]
@@ -218,10 +385,87 @@ where
     gens: &SparsePolyCommitmentGens<G>,
     transcript: &mut Transcript,
   ) -> Result<(), ProofVerifyError> {
-    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name());
+    self.verify_core(commitment, eq_randomness, gens, transcript)?;
+    Ok(())
+  }
 
-    debug_assert_eq!(eq_randomness.len(), log2(commitment.s) as usize);
+  /// Verifies the proof as `verify` does, additionally returning the memory-checking multiset
+  /// hashes so that external protocols can consume them as public inputs.
+  pub fn verify_with_memory_state(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+  ) -> Result<MemoryStateClaim<G::ScalarField>, ProofVerifyError> {
+    let r_mem_check = self.verify_core(commitment, eq_randomness, gens, transcript)?;
+    Ok(self.memory_check.memory_state_claim(&r_mem_check))
+  }
 
+  /// Verifies the proof as `verify` does, additionally reporting the wall-clock time spent in each
+  /// subprotocol and the proof size.
+  pub fn verify_with_profiling(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+  ) -> Result<VerifierProfile, ProofVerifyError> {
+    let mut profile = VerifierProfile {
+      proof_size: self.compressed_size(),
+      ..Default::default()
+    };
+    self.begin_verify(commitment, eq_randomness, transcript)?;
+
+    let start = Instant::now();
+    let r_z = self.verify_primary_sumcheck(commitment, eq_randomness, transcript)?;
+    profile.primary_sumcheck = start.elapsed();
+
+    let start = Instant::now();
+    self.verify_derefs_opening(&r_z, gens, transcript)?;
+    profile.derefs_opening = start.elapsed();
+
+    let start = Instant::now();
+    self.verify_memory_checking(commitment, gens, transcript)?;
+    profile.memory_checking = start.elapsed();
+
+    Ok(profile)
+  }
+
+  /// Runs every verification stage without profiling. Returns the fingerprint randomness
+  /// (gamma, tau) used by memory checking.
+  fn verify_core(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &[G::ScalarField],
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+  ) -> Result<(G::ScalarField, G::ScalarField), ProofVerifyError> {
+    self.begin_verify(commitment, eq_randomness, transcript)?;
+    let r_z = self.verify_primary_sumcheck(commitment, eq_randomness, transcript)?;
+    self.verify_derefs_opening(&r_z, gens, transcript)?;
+    self.verify_memory_checking(commitment, gens, transcript)
+  }
+
+  fn begin_verify(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &[G::ScalarField],
+    transcript: &mut Transcript,
+  ) -> Result<(), ProofVerifyError> {
+    <Transcript as ProofTranscript<G>>::append_protocol_name(transcript, Self::protocol_name(), 0);
+
+    Self::validate_parameters(commitment, eq_randomness)
+  }
+
+  /// Absorbs the E_i commitment and the claimed evaluation, then checks the primary sumcheck.
+  /// Returns the sumcheck's random point r_z.
+  fn verify_primary_sumcheck(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &[G::ScalarField],
+    transcript: &mut Transcript,
+  ) -> Result<Vec<G::ScalarField>, ProofVerifyError> {
     // add claims to transcript and obtain challenges for randomized mem-check circuit
     self
       .comm_derefs
@@ -232,45 +476,171 @@ where
       b"claim_eval_scalar_product",
       &self.primary_sumcheck.claimed_evaluation,
     );
+    if transcript_digest(transcript, b"digest_post_commitments")
+      != self.transcript_digests.post_commitments
+    {
+      return Err(ProofVerifyError::TranscriptDigestMismatch(
+        "post_commitments",
+      ));
+    }
 
     let (claim_last, r_z) = self.primary_sumcheck.proof.verify::<G, Transcript>(
       self.primary_sumcheck.claimed_evaluation,
       commitment.s.log_2(),
       S::sumcheck_poly_degree(),
       transcript,
+      PRIMARY_INSTANCE,
     )?;
 
     // Verify that eq(r, r_z) * g(E_1(r_z) * ... * E_c(r_z)) = claim_last
-    let eq_eval = EqPolynomial::new(eq_randomness.clone()).evaluate(&r_z);
-    assert_eq!(
-      eq_eval * S::combine_lookups(&self.primary_sumcheck.eval_derefs),
-      claim_last,
-      "Primary sumcheck check failed."
-    );
+    let eq_eval = EqPolynomial::new(eq_randomness.to_vec()).evaluate(&r_z);
+    if eq_eval * S::combine_lookups(&self.primary_sumcheck.eval_derefs) != claim_last {
+      return Err(ProofVerifyError::PrimarySumcheckFailed);
+    }
+    Ok(r_z)
+  }
 
+  /// Checks the opening of the E_i polynomials at the primary sumcheck's random point r_z.
+  fn verify_derefs_opening(
+    &self,
+    r_z: &[G::ScalarField],
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+  ) -> Result<(), ProofVerifyError> {
     self.primary_sumcheck.proof_derefs.verify(
-      &r_z,
+      r_z,
       &self.primary_sumcheck.eval_derefs,
       &gens.gens_derefs,
       &self.comm_derefs,
       transcript,
+      PRIMARY_INSTANCE,
     )?;
+    if transcript_digest(transcript, b"digest_post_sumcheck")
+      != self.transcript_digests.post_sumcheck
+    {
+      return Err(ProofVerifyError::TranscriptDigestMismatch("post_sumcheck"));
+    }
+    Ok(())
+  }
 
+  /// Checks the memory-checking proof. Returns the fingerprint randomness (gamma, tau) it used.
+  fn verify_memory_checking(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+  ) -> Result<(G::ScalarField, G::ScalarField), ProofVerifyError> {
     // produce a random element from the transcript for hash function
     let r_mem_check =
       <Transcript as ProofTranscript<G>>::challenge_vector(transcript, b"challenge_r_hash", 2);
+    let r_mem_check = (r_mem_check[0], r_mem_check[1]);
 
     self.memory_check.verify(
       commitment,
       &self.comm_derefs,
       gens,
-      &(r_mem_check[0], r_mem_check[1]),
+      &r_mem_check,
       commitment.s,
       transcript,
-    )
+    )?;
+    Ok(r_mem_check)
+  }
+
+  /// Digests of the transcript state at this proof's checkpoints, which the verifier recomputes
+  /// and checks.
+  pub fn transcript_digests(&self) -> &TranscriptDigests {
+    &self.transcript_digests
+  }
+
+  /// Checks the commitment's parameter header (s, log_m, m) against this proof's type-level
+  /// parameters and the evaluation point, so that a mismatch is reported as an error rather than
+  /// surfacing later as an opaque sumcheck or memory-checking failure.
+  fn validate_parameters(
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &[G::ScalarField],
+  ) -> Result<(), ProofVerifyError> {
+    if commitment.m != M {
+      return Err(ProofVerifyError::InvalidInputLength(M, commitment.m));
+    }
+    if commitment.log_m != M.log_2() {
+      return Err(ProofVerifyError::InvalidInputLength(
+        M.log_2(),
+        commitment.log_m,
+      ));
+    }
+    if commitment.s < 2 || !commitment.s.is_power_of_two() {
+      return Err(ProofVerifyError::InvalidSparsity(commitment.s));
+    }
+    if eq_randomness.len() != commitment.s.log_2() {
+      return Err(ProofVerifyError::InvalidInputLength(
+        commitment.s.log_2(),
+        eq_randomness.len(),
+      ));
+    }
+    Ok(())
+  }
+
+  /// Verifies the proof as `verify` does, but first rejects it if the parameters provide fewer
+  /// than `min_security_bits` bits of statistical soundness (see `security_bits`).
+  pub fn verify_with_security_level(
+    &self,
+    commitment: &SparsePolynomialCommitment<G>,
+    eq_randomness: &Vec<G::ScalarField>,
+    gens: &SparsePolyCommitmentGens<G>,
+    transcript: &mut Transcript,
+    min_security_bits: usize,
+  ) -> Result<(), ProofVerifyError> {
+    // `security_bits` assumes a well-formed sparsity, so check the header before trusting `s`
+    Self::validate_parameters(commitment, eq_randomness)?;
+    let security_bits = Self::security_bits(commitment.s)?;
+    if security_bits < min_security_bits as f64 {
+      return Err(ProofVerifyError::InsufficientSecurity(
+        min_security_bits,
+        security_bits as usize,
+      ));
+    }
+    self.verify(commitment, eq_randomness, gens, transcript)
+  }
+
+  /// Bits of statistical soundness, i.e. -log2(error), of the Surge protocol for `s` lookups.
+  /// The error is a union bound over every verifier challenge, each of which fails with
+  /// probability at most (degree / |F|) by Schwartz-Zippel:
+  /// - Primary sumcheck: log(s) rounds of degree `S::sumcheck_poly_degree()`.
+  /// - Grand products: layer j of a log(n)-layer circuit is a j-round cubic sumcheck, plus one
+  ///   challenge to batch the circuits and one to condense the layer's two claims.
+  /// - Fingerprints: each memory's multiset equality is a polynomial identity in (gamma, tau) of
+  ///   degree at most 2 * (M + s).
+  /// - n-to-1 reductions of the claimed evaluations in the hash layer and table openings.
+  ///
+  /// The Hyrax-style polynomial commitments are computationally sound under the discrete log
+  /// assumption in `G` and are not counted here.
+  ///
+  /// Fails with `InvalidSparsity` if `s` is too large to pad to a power of two.
+  pub fn security_bits(s: usize) -> Result<f64, ProofVerifyError> {
+    if s.checked_next_power_of_two().is_none() {
+      return Err(ProofVerifyError::InvalidSparsity(s));
+    }
+    let s = padded_sparsity(s);
+    let log_s = s.log_2();
+    let log_m = M.log_2();
+
+    let primary_sumcheck = log_s * S::sumcheck_poly_degree();
+    let grand_product =
+      |num_layers: usize| 3 * num_layers * num_layers.saturating_sub(1) / 2 + 2 * num_layers;
+    let grand_products = grand_product(log_s) + grand_product(log_m);
+    // Computed in floating point, as an `s` near usize::MAX would overflow (M + s)
+    let fingerprints = (S::NUM_MEMORIES * 2) as f64 * (M as f64 + s as f64);
+    let reductions = (2 * C).next_power_of_two().log_2()
+      + C.next_power_of_two().log_2()
+      + 2 * S::NUM_MEMORIES.next_power_of_two().log_2();
+
+    let num_bad_events = (primary_sumcheck + grand_products + reductions) as f64 + fingerprints;
+    // |F| >= 2^(MODULUS_BIT_SIZE - 1)
+    let log_field_size = (G::ScalarField::MODULUS_BIT_SIZE - 1) as f64;
+    Ok(log_field_size - num_bad_events.log2())
   }
 
-  fn protocol_name() -> &'static [u8] {
-    b"Lasso SparsePolynomialEvaluationProof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::SparsePolynomialEvaluation
   }
 }
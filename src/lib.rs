@@ -14,3 +14,5 @@ mod utils;
 
 #[cfg(test)]
 mod e2e_test;
+#[cfg(test)]
+mod test_vectors;
@@ -1,4 +1,6 @@
 use liblasso::benches::bench::{benchmarks, BenchType};
+#[cfg(feature = "chrome")]
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::{self, fmt::format::FmtSpan};
 
 use clap::Parser;
@@ -10,6 +12,11 @@ struct Cli {
   #[clap(long, short, action)]
   chart: bool,
 
+  /// Whether to write a Chrome trace file, viewable in chrome://tracing or Perfetto
+  #[cfg(feature = "chrome")]
+  #[clap(long, action)]
+  chrome: bool,
+
   /// Type of benchmark to run
   #[clap(long, value_enum)]
   name: BenchType,
@@ -22,17 +29,28 @@ fn main() {
     for (span, bench) in benchmarks(args.name).iter() {
       tracing_texray::examine(span.to_owned()).in_scope(bench);
     }
-  } else {
-    let collector = tracing_subscriber::fmt()
-      .with_max_level(tracing::Level::TRACE)
-      .with_span_events(FmtSpan::CLOSE)
-      .finish();
-    tracing::subscriber::set_global_default(collector).expect("setting tracing default failed");
+    return;
+  }
+
+  #[cfg(feature = "chrome")]
+  if args.chrome {
+    let (chrome_layer, _guard) = liblasso::benches::chrome::chrome_layer();
+    tracing_subscriber::registry().with(chrome_layer).init();
     for (span, bench) in benchmarks(args.name).iter() {
-      span.to_owned().in_scope(|| {
-        bench();
-        tracing::info!("Bench Complete");
-      });
+      span.to_owned().in_scope(bench);
     }
+    return;
+  }
+
+  let collector = tracing_subscriber::fmt()
+    .with_max_level(tracing::Level::TRACE)
+    .with_span_events(FmtSpan::CLOSE)
+    .finish();
+  tracing::subscriber::set_global_default(collector).expect("setting tracing default failed");
+  for (span, bench) in benchmarks(args.name).iter() {
+    span.to_owned().in_scope(|| {
+      bench();
+      tracing::info!("Bench Complete");
+    });
   }
 }
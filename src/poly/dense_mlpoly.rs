@@ -7,7 +7,7 @@ use crate::subprotocols::dot_product::{DotProductProofGens, DotProductProofLog};
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 use crate::utils::random::RandomTape;
-use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
+use crate::utils::transcript::{AppendToTranscript, ProofTranscript, ProtocolDomain};
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_serialize::*;
@@ -24,6 +24,12 @@ use crate::msm::VariableBaseMSM;
 #[cfg(feature = "multicore")]
 use rayon::prelude::*;
 
+/// Minimum number of evaluations bound per parallel task: 4096 32-byte field elements from each
+/// half (or 4096 adjacent pairs, when binding the bottom variable) fit comfortably in a typical L2
+/// cache.
+#[cfg(feature = "multicore")]
+const BIND_TILE_SIZE: usize = 1 << 12;
+
 #[derive(Debug, Clone)]
 pub struct DensePolynomial<F> {
   num_vars: usize, // the number of variables in the multilinear polynomial
@@ -206,17 +212,47 @@ impl<F: PrimeField> DensePolynomial<F> {
     bound_vals
   }
 
+  /// Binds the top variable to `r`. Each evaluation in the low half only depends on the matching
+  /// evaluation in the high half, so the two halves are streamed in contiguous tiles, which run
+  /// in parallel under `multicore`.
   pub fn bound_poly_var_top(&mut self, r: &F) {
     let n = self.len() / 2;
-    for i in 0..n {
-      self.Z[i] = self.Z[i] + *r * (self.Z[i + n] - self.Z[i]);
-    }
+    let (low, high) = self.Z.split_at_mut(n);
+
+    #[cfg(feature = "multicore")]
+    low
+      .par_iter_mut()
+      .zip(high[..n].par_iter())
+      .with_min_len(BIND_TILE_SIZE)
+      .for_each(|(low, high)| *low += *r * (*high - *low));
+
+    #[cfg(not(feature = "multicore"))]
+    low
+      .iter_mut()
+      .zip(high[..n].iter())
+      .for_each(|(low, high)| *low += *r * (*high - *low));
+
     self.num_vars -= 1;
     self.len = n;
   }
 
   pub fn bound_poly_var_bot(&mut self, r: &F) {
     let n = self.len() / 2;
+
+    // Z[i] is computed from Z[2i] and Z[2i + 1], which a parallel in-place bind could already have
+    // overwritten, so large polynomials are bound into a fresh buffer in tiles instead.
+    #[cfg(feature = "multicore")]
+    if n >= BIND_TILE_SIZE {
+      self.Z = self.Z[..2 * n]
+        .par_chunks_exact(2)
+        .with_min_len(BIND_TILE_SIZE)
+        .map(|pair| pair[0] + *r * (pair[1] - pair[0]))
+        .collect();
+      self.num_vars -= 1;
+      self.len = n;
+      return;
+    }
+
     for i in 0..n {
       self.Z[i] = self.Z[2 * i] + *r * (self.Z[2 * i + 1] - self.Z[2 * i]);
     }
@@ -234,6 +270,30 @@ impl<F: PrimeField> DensePolynomial<F> {
     compute_dotproduct(&self.Z, &chis)
   }
 
+  /// Evaluates the polynomial given `chis`, the evaluations of eq(r, x) over the hypercube as
+  /// computed by `EqPolynomial::evals`, in O(n) time.
+  pub fn evaluate_at_chi(&self, chis: &[F]) -> F {
+    assert_eq!(chis.len(), self.Z.len());
+    compute_dotproduct(&self.Z, chis)
+  }
+
+  /// Evaluates each of `polys` at the same point `r`. The eq evaluations are computed once and
+  /// shared across all of the polynomials, which are then evaluated in parallel.
+  #[tracing::instrument(skip_all, name = "DensePolynomial.batch_evaluate")]
+  pub fn batch_evaluate(polys: &[&DensePolynomial<F>], r: &[F]) -> Vec<F> {
+    polys
+      .iter()
+      .for_each(|poly| assert_eq!(poly.get_num_vars(), r.len()));
+    let chis = EqPolynomial::new(r.to_vec()).evals();
+
+    #[cfg(feature = "multicore")]
+    let iterator = polys.par_iter();
+    #[cfg(not(feature = "multicore"))]
+    let iterator = polys.iter();
+
+    iterator.map(|poly| poly.evaluate_at_chi(&chis)).collect()
+  }
+
   fn vec(&self) -> &Vec<F> {
     &self.Z
   }
@@ -260,12 +320,16 @@ impl<F: PrimeField> DensePolynomial<F> {
     DensePolynomial::new(Z)
   }
 
+  /// Converts access indices or timestamps into a polynomial. The conversion into Montgomery
+  /// form dominates for s- and m-sized inputs, so it runs in parallel under `multicore`.
   pub fn from_usize(Z: &[usize]) -> Self {
-    DensePolynomial::new(
-      (0..Z.len())
-        .map(|i| F::from(Z[i] as u64))
-        .collect::<Vec<F>>(),
-    )
+    #[cfg(feature = "multicore")]
+    let evals = Z.par_iter().map(|z| F::from(*z as u64)).collect::<Vec<F>>();
+
+    #[cfg(not(feature = "multicore"))]
+    let evals = Z.iter().map(|z| F::from(*z as u64)).collect::<Vec<F>>();
+
+    DensePolynomial::new(evals)
   }
 }
 
@@ -294,8 +358,8 @@ pub struct PolyEvalProof<G: CurveGroup> {
 }
 
 impl<G: CurveGroup> PolyEvalProof<G> {
-  fn protocol_name() -> &'static [u8] {
-    b"polynomial evaluation proof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::PolyEval
   }
 
   #[tracing::instrument(skip_all, name = "DensePolyEval.prove")]
@@ -307,11 +371,13 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     blind_Zr_opt: Option<&G::ScalarField>, // specifies a blind for Zr
     gens: &PolyCommitmentGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
   ) -> (PolyEvalProof<G>, G) {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       PolyEvalProof::<G>::protocol_name(),
+      instance,
     );
 
     // assert vectors are of the right size
@@ -347,6 +413,7 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     let (proof, _C_LR, C_Zr_prime) = DotProductProofLog::prove(
       &gens.gens,
       transcript,
+      instance,
       random_tape,
       &LZ,
       &LZ_blind,
@@ -362,6 +429,7 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     &self,
     gens: &PolyCommitmentGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     r: &[G::ScalarField], // point at which the polynomial is evaluated
     C_Zr: &G,             // commitment to \widetilde{Z}(r)
     comm: &PolyCommitment<G>,
@@ -369,6 +437,7 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       PolyEvalProof::<G>::protocol_name(),
+      instance,
     );
 
     // compute L and R
@@ -382,13 +451,14 @@ impl<G: CurveGroup> PolyEvalProof<G> {
 
     self
       .proof
-      .verify(R.len(), &gens.gens, transcript, &R, &C_LZ, C_Zr)
+      .verify(R.len(), &gens.gens, transcript, instance, &R, &C_LZ, C_Zr)
   }
 
   pub fn verify_plain(
     &self,
     gens: &PolyCommitmentGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     r: &[G::ScalarField], // point at which the polynomial is evaluated
     Zr: &G::ScalarField,  // evaluation \widetilde{Z}(r)
     comm: &PolyCommitment<G>,
@@ -396,7 +466,7 @@ impl<G: CurveGroup> PolyEvalProof<G> {
     // compute a commitment to Zr with a blind of zero
     let C_Zr = Zr.commit(&G::ScalarField::zero(), &gens.gens.gens_1);
 
-    self.verify(gens, transcript, r, &C_Zr, comm)
+    self.verify(gens, transcript, instance, r, &C_Zr, comm)
   }
 }
 
@@ -582,6 +652,66 @@ mod tests {
     assert_eq!(R, R2);
   }
 
+  #[test]
+  fn batch_evaluate() {
+    let mut prng = test_rng();
+
+    let num_vars: usize = 6;
+    let polys: Vec<DensePolynomial<Fr>> = (0..5)
+      .map(|_| DensePolynomial::new((0..num_vars.pow2()).map(|_| Fr::rand(&mut prng)).collect()))
+      .collect();
+    let r: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut prng)).collect();
+
+    let batched = DensePolynomial::batch_evaluate(&polys.iter().collect::<Vec<_>>(), &r);
+    let expected: Vec<Fr> = polys.iter().map(|poly| poly.evaluate(&r)).collect();
+    assert_eq!(batched, expected);
+  }
+
+  #[test]
+  fn bound_poly_var_top() {
+    let mut prng = test_rng();
+
+    let num_vars: usize = 14;
+    let mut poly =
+      DensePolynomial::new((0..num_vars.pow2()).map(|_| Fr::rand(&mut prng)).collect());
+    let r: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut prng)).collect();
+    let expected = poly.evaluate(&r);
+
+    for r_i in r.iter() {
+      poly.bound_poly_var_top(r_i);
+    }
+    assert_eq!(poly.len(), 1);
+    assert_eq!(poly[0], expected);
+  }
+
+  #[test]
+  fn bound_poly_var_bot() {
+    let mut prng = test_rng();
+
+    let num_vars: usize = 14;
+    let mut poly =
+      DensePolynomial::new((0..num_vars.pow2()).map(|_| Fr::rand(&mut prng)).collect());
+    let r: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut prng)).collect();
+    let expected = poly.evaluate(&r);
+
+    for r_i in r.iter().rev() {
+      poly.bound_poly_var_bot(r_i);
+    }
+    assert_eq!(poly.len(), 1);
+    assert_eq!(poly[0], expected);
+  }
+
+  #[test]
+  fn from_usize() {
+    let Z: Vec<usize> = (0..64).map(|i| (i * 37) % 19).collect();
+    let expected: Vec<Fr> = Z.iter().map(|z| Fr::from(*z as u64)).collect();
+
+    let from_usize = DensePolynomial::<Fr>::from_usize(&Z);
+    for (i, expected) in expected.iter().enumerate() {
+      assert_eq!(from_usize[i], *expected);
+    }
+  }
+
   #[test]
   fn check_polynomial_commit() {
     check_polynomial_commit_helper::<G1Projective>()
@@ -614,13 +744,21 @@ mod tests {
       None,
       &gens,
       &mut prover_transcript,
+      0,
       &mut random_tape,
     );
 
     let mut verifier_transcript = Transcript::new(b"example");
 
     assert!(proof
-      .verify(&gens, &mut verifier_transcript, &r, &C_Zr, &poly_commitment)
+      .verify(
+        &gens,
+        &mut verifier_transcript,
+        0,
+        &r,
+        &C_Zr,
+        &poly_commitment
+      )
       .is_ok());
   }
 
@@ -2,6 +2,15 @@ use ark_ff::PrimeField;
 
 use crate::utils::math::Math;
 
+#[cfg(feature = "multicore")]
+use rayon::prelude::*;
+
+/// Minimum number of table entries doubled per parallel task in `evals`, so that the early rounds,
+/// whose halves hold only a handful of entries, are not split into tasks smaller than their
+/// scheduling overhead.
+#[cfg(feature = "multicore")]
+const EVALS_MIN_LEN: usize = 1 << 12;
+
 pub struct EqPolynomial<F> {
   r: Vec<F>,
 }
@@ -18,21 +27,33 @@ impl<F: PrimeField> EqPolynomial<F> {
       .product()
   }
 
+  /// Computes the evaluations of eq(r, x) for all x in {0, 1}^|r|.
+  /// Each round doubles the table by writing the upper half from the lower half, which
+  /// allows the round to be split across threads. Iterating over `r` in reverse keeps
+  /// r[0] as the most significant bit of the index.
   #[tracing::instrument(skip_all, name = "EqPolynomial.evals")]
   pub fn evals(&self) -> Vec<F> {
     let ell = self.r.len();
 
     let mut evals: Vec<F> = vec![F::one(); ell.pow2()];
     let mut size = 1;
-    for j in 0..ell {
-      // in each iteration, we double the size of chis
+    for r_j in self.r.iter().rev() {
+      let (evals_low, evals_high) = evals.split_at_mut(size);
+      let evals_high = &mut evals_high[..size];
+
+      #[cfg(feature = "multicore")]
+      let iterator = evals_low
+        .par_iter_mut()
+        .zip(evals_high.par_iter_mut())
+        .with_min_len(EVALS_MIN_LEN);
+      #[cfg(not(feature = "multicore"))]
+      let iterator = evals_low.iter_mut().zip(evals_high.iter_mut());
+
+      iterator.for_each(|(low, high)| {
+        *high = *low * r_j;
+        *low -= *high;
+      });
       size *= 2;
-      for i in (0..size).rev().step_by(2) {
-        // copy each element from the prior iteration twice
-        let scalar = evals[i / 2];
-        evals[i] = scalar * self.r[j];
-        evals[i - 1] = scalar - evals[i];
-      }
     }
     evals
   }
@@ -51,3 +72,23 @@ impl<F: PrimeField> EqPolynomial<F> {
     (L, R)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ark_curve25519::Fr;
+  use ark_std::{test_rng, UniformRand};
+
+  #[test]
+  fn evals_matches_evaluate() {
+    let mut prng = test_rng();
+    let num_vars = 5;
+    let r: Vec<Fr> = (0..num_vars).map(|_| Fr::rand(&mut prng)).collect();
+    let eq = EqPolynomial::new(r);
+    let evals = eq.evals();
+    for (i, eval) in evals.iter().enumerate() {
+      let x = crate::utils::index_to_field_bitvector(i, num_vars);
+      assert_eq!(*eval, eq.evaluate(&x));
+    }
+  }
+}
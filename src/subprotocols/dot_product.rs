@@ -3,7 +3,7 @@ use crate::poly::commitments::{Commitments, MultiCommitGens};
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::math::Math;
 use crate::utils::random::RandomTape;
-use crate::utils::transcript::ProofTranscript;
+use crate::utils::transcript::{ProofTranscript, ProtocolDomain};
 use ark_ec::CurveGroup;
 use ark_serialize::*;
 use merlin::Transcript;
@@ -18,8 +18,8 @@ pub struct DotProductProof<G: CurveGroup> {
 }
 
 impl<G: CurveGroup> DotProductProof<G> {
-  fn protocol_name() -> &'static [u8] {
-    b"dot product proof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::DotProduct
   }
 
   pub fn compute_dotproduct(a: &[G::ScalarField], b: &[G::ScalarField]) -> G::ScalarField {
@@ -32,6 +32,7 @@ impl<G: CurveGroup> DotProductProof<G> {
     gens_1: &MultiCommitGens<G>,
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
     x_vec: &[G::ScalarField],
     blind_x: &G::ScalarField,
@@ -42,6 +43,7 @@ impl<G: CurveGroup> DotProductProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProof::<G>::protocol_name(),
+      instance,
     );
 
     let n = x_vec.len();
@@ -97,6 +99,7 @@ impl<G: CurveGroup> DotProductProof<G> {
     gens_1: &MultiCommitGens<G>,
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     a: &[G::ScalarField],
     Cx: &G,
     Cy: &G,
@@ -111,6 +114,7 @@ impl<G: CurveGroup> DotProductProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProof::<G>::protocol_name(),
+      instance,
     );
 
     <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cx", Cx);
@@ -159,14 +163,15 @@ pub struct DotProductProofLog<G: CurveGroup> {
 }
 
 impl<G: CurveGroup> DotProductProofLog<G> {
-  fn protocol_name() -> &'static [u8] {
-    b"dot product proof (log)"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::DotProductLog
   }
 
   #[tracing::instrument(skip_all, name = "DotProductProofLog.prove")]
   pub fn prove(
     gens: &DotProductProofGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
     x_vec: &[G::ScalarField],
     blind_x: &G::ScalarField,
@@ -177,6 +182,7 @@ impl<G: CurveGroup> DotProductProofLog<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProofLog::<G>::protocol_name(),
+      instance,
     );
 
     let n = x_vec.len();
@@ -253,6 +259,7 @@ impl<G: CurveGroup> DotProductProofLog<G> {
     n: usize,
     gens: &DotProductProofGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     a: &[G::ScalarField],
     Cx: &G,
     Cy: &G,
@@ -263,6 +270,7 @@ impl<G: CurveGroup> DotProductProofLog<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       DotProductProofLog::<G>::protocol_name(),
+      instance,
     );
     <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cx", Cx);
     <Transcript as ProofTranscript<G>>::append_point(transcript, b"Cy", Cy);
@@ -332,6 +340,7 @@ mod tests {
       &gens_1,
       &gens_1024,
       &mut prover_transcript,
+      0,
       &mut random_tape,
       &x,
       &r_x,
@@ -342,7 +351,15 @@ mod tests {
 
     let mut verifier_transcript = Transcript::new(b"example");
     assert!(proof
-      .verify(&gens_1, &gens_1024, &mut verifier_transcript, &a, &Cx, &Cy)
+      .verify(
+        &gens_1,
+        &gens_1024,
+        &mut verifier_transcript,
+        0,
+        &a,
+        &Cx,
+        &Cy
+      )
       .is_ok());
   }
 
@@ -369,6 +386,7 @@ mod tests {
     let (proof, Cx, Cy) = DotProductProofLog::prove(
       &gens,
       &mut prover_transcript,
+      0,
       &mut random_tape,
       &x,
       &r_x,
@@ -379,7 +397,7 @@ mod tests {
 
     let mut verifier_transcript = Transcript::new(b"example");
     assert!(proof
-      .verify(n, &gens, &mut verifier_transcript, &a, &Cx, &Cy)
+      .verify(n, &gens, &mut verifier_transcript, 0, &a, &Cx, &Cy)
       .is_ok());
   }
 }
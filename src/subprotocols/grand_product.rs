@@ -3,12 +3,14 @@
 use super::sumcheck::SumcheckInstanceProof;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::eq_poly::EqPolynomial;
+use crate::utils::errors::{Cancelled, ProofVerifyError};
 use crate::utils::math::Math;
-use crate::utils::transcript::ProofTranscript;
+use crate::utils::transcript::{nested_instance, ProofTranscript, ProtocolDomain};
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_serialize::*;
 use merlin::Transcript;
+use std::sync::atomic::AtomicBool;
 
 #[derive(Debug)]
 pub struct GrandProductCircuit<F> {
@@ -39,6 +41,10 @@ impl<F: PrimeField> GrandProductCircuit<F> {
     let mut left_vec: Vec<DensePolynomial<F>> = Vec::new();
     let mut right_vec: Vec<DensePolynomial<F>> = Vec::new();
 
+    assert!(
+      poly.len() >= 2,
+      "A grand product circuit needs at least two leaves"
+    );
     let num_layers = poly.len().log_2() as usize;
     let (outp_left, outp_right) = poly.split(poly.len() / 2);
 
@@ -80,31 +86,43 @@ impl<F: PrimeField> LayerProofBatched<F> {
     num_rounds: usize,
     degree_bound: usize,
     transcript: &mut T,
-  ) -> (F, Vec<F>)
+    instance: u64,
+  ) -> Result<(F, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
     self
       .proof
-      .verify::<G, T>(claim, num_rounds, degree_bound, transcript)
-      .unwrap()
+      .verify::<G, T>(claim, num_rounds, degree_bound, transcript, instance)
   }
 }
 
 #[derive(Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BatchedGrandProductArgument<F: PrimeField> {
-  proof: Vec<LayerProofBatched<F>>,
+  pub(crate) proof: Vec<LayerProofBatched<F>>,
 }
 
 impl<F: PrimeField> BatchedGrandProductArgument<F> {
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::BatchedGrandProduct
+  }
+
   #[tracing::instrument(skip_all, name = "BatchedGrandProductArgument.prove")]
   pub fn prove<G>(
     grand_product_circuits: &mut Vec<&mut GrandProductCircuit<F>>,
     transcript: &mut Transcript,
-  ) -> (Self, Vec<F>)
+    instance: u64,
+    cancel: &AtomicBool,
+  ) -> Result<(Self, Vec<F>), Cancelled>
   where
     G: CurveGroup<ScalarField = F>,
   {
+    <Transcript as ProofTranscript<G>>::append_protocol_name(
+      transcript,
+      Self::protocol_name(),
+      instance,
+    );
+
     assert!(!grand_product_circuits.is_empty());
 
     let mut proof_layers: Vec<LayerProofBatched<F>> = Vec::new();
@@ -115,6 +133,8 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
 
     let mut rand = Vec::new();
     for layer_id in (0..num_layers).rev() {
+      Cancelled::check(cancel)?;
+
       // prepare parallel instance that share poly_C first
       let len = grand_product_circuits[0].left_vec[layer_id].len()
         + grand_product_circuits[0].right_vec[layer_id].len();
@@ -156,22 +176,21 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
         &coeff_vec,
         comb_func_prod,
         transcript,
-      );
+        nested_instance(instance, proof_layers.len()),
+        cancel,
+      )?;
 
       let (claims_prod_left, claims_prod_right, _claims_eq) = claims_prod;
-      for i in 0..grand_product_circuits.len() {
-        <Transcript as ProofTranscript<G>>::append_scalar(
-          transcript,
-          b"claim_prod_left",
-          &claims_prod_left[i],
-        );
-
-        <Transcript as ProofTranscript<G>>::append_scalar(
-          transcript,
-          b"claim_prod_right",
-          &claims_prod_right[i],
-        );
-      }
+      <Transcript as ProofTranscript<G>>::append_scalars(
+        transcript,
+        b"claims_prod_left",
+        &claims_prod_left,
+      );
+      <Transcript as ProofTranscript<G>>::append_scalars(
+        transcript,
+        b"claims_prod_right",
+        &claims_prod_right,
+      );
 
       // produce a random challenge to condense two claims into a single claim
       let r_layer =
@@ -192,12 +211,12 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
       });
     }
 
-    (
+    Ok((
       BatchedGrandProductArgument {
         proof: proof_layers,
       },
       rand,
-    )
+    ))
   }
 
   pub fn verify<G, T: ProofTranscript<G>>(
@@ -205,13 +224,21 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
     claims_prod_vec: &Vec<F>,
     len: usize,
     transcript: &mut T,
-  ) -> (Vec<F>, Vec<F>)
+    instance: u64,
+  ) -> Result<(Vec<F>, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
+    transcript.append_protocol_name(Self::protocol_name(), instance);
+
     let num_layers = len.log_2() as usize;
     let mut rand: Vec<F> = Vec::new();
-    assert_eq!(self.proof.len(), num_layers);
+    if self.proof.len() != num_layers {
+      return Err(ProofVerifyError::InvalidInputLength(
+        num_layers,
+        self.proof.len(),
+      ));
+    }
 
     let mut claims_to_verify = claims_prod_vec.to_owned();
     for (num_rounds, i) in (0..num_layers).enumerate() {
@@ -224,19 +251,30 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
         .map(|i| claims_to_verify[i] * coeff_vec[i])
         .sum();
 
-      let (claim_last, rand_prod) = self.proof[i].verify::<G, T>(claim, num_rounds, 3, transcript);
+      let (claim_last, rand_prod) = self.proof[i].verify::<G, T>(
+        claim,
+        num_rounds,
+        3,
+        transcript,
+        nested_instance(instance, i),
+      )?;
 
       let claims_prod_left = &self.proof[i].claims_prod_left;
       let claims_prod_right = &self.proof[i].claims_prod_right;
-      assert_eq!(claims_prod_left.len(), claims_prod_vec.len());
-      assert_eq!(claims_prod_right.len(), claims_prod_vec.len());
-
-      for i in 0..claims_prod_vec.len() {
-        transcript.append_scalar(b"claim_prod_left", &claims_prod_left[i]);
-        transcript.append_scalar(b"claim_prod_right", &claims_prod_right[i]);
+      for claims in [claims_prod_left, claims_prod_right] {
+        if claims.len() != claims_prod_vec.len() {
+          return Err(ProofVerifyError::InvalidInputLength(
+            claims_prod_vec.len(),
+            claims.len(),
+          ));
+        }
       }
 
-      assert_eq!(rand.len(), rand_prod.len());
+      transcript.append_scalars(b"claims_prod_left", claims_prod_left);
+      transcript.append_scalars(b"claims_prod_right", claims_prod_right);
+
+      // Both have `num_rounds` entries, which the layer's sumcheck has already checked
+      debug_assert_eq!(rand.len(), rand_prod.len());
       let eq: F = (0..rand.len())
         .map(|i| rand[i] * rand_prod[i] + (F::one() - rand[i]) * (F::one() - rand_prod[i]))
         .product();
@@ -244,7 +282,9 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
         .map(|i| coeff_vec[i] * (claims_prod_left[i] * claims_prod_right[i] * eq))
         .sum();
 
-      assert_eq!(claim_expected, claim_last);
+      if claim_expected != claim_last {
+        return Err(ProofVerifyError::GrandProductLayerFailed(i));
+      }
 
       // produce a random challenge
       let r_layer = transcript.challenge_scalar(b"challenge_r_layer");
@@ -257,7 +297,7 @@ impl<F: PrimeField> BatchedGrandProductArgument<F> {
       ext.extend(rand_prod);
       rand = ext;
     }
-    (claims_to_verify, rand)
+    Ok((claims_to_verify, rand))
   }
 }
 
@@ -275,10 +315,17 @@ mod grand_product_circuit_tests {
 
     let mut transcript = Transcript::new(b"test_transcript");
     let mut circuits_vec = vec![&mut factorial_circuit];
-    let (proof, _) =
-      BatchedGrandProductArgument::prove::<G1Projective>(&mut circuits_vec, &mut transcript);
+    let (proof, _) = BatchedGrandProductArgument::prove::<G1Projective>(
+      &mut circuits_vec,
+      &mut transcript,
+      0,
+      &AtomicBool::new(false),
+    )
+    .unwrap();
 
     let mut transcript = Transcript::new(b"test_transcript");
-    proof.verify::<G1Projective, _>(&expected_eval, 4, &mut transcript);
+    assert!(proof
+      .verify::<G1Projective, _>(&expected_eval, 4, &mut transcript, 0)
+      .is_ok());
   }
 }
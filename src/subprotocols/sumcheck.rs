@@ -5,13 +5,16 @@ use crate::poly::commitments::MultiCommitGens;
 use crate::poly::dense_mlpoly::DensePolynomial;
 use crate::poly::unipoly::{CompressedUniPoly, UniPoly};
 use crate::subprotocols::dot_product::DotProductProof;
-use crate::utils::errors::ProofVerifyError;
-use crate::utils::transcript::{AppendToTranscript, ProofTranscript};
+use crate::utils::errors::{Cancelled, ProofVerifyError};
+use crate::utils::transcript::{
+  nested_instance, AppendToTranscript, ProofTranscript, ProtocolDomain,
+};
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_serialize::*;
 use ark_std::One;
 use merlin::Transcript;
+use std::sync::atomic::AtomicBool;
 
 #[cfg(feature = "ark-msm")]
 use ark_ec::VariableBaseMSM;
@@ -35,11 +38,19 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     coeffs: &[F],
     comb_func: Func,
     transcript: &mut Transcript,
-  ) -> (Self, Vec<F>, (Vec<F>, Vec<F>, F))
+    instance: u64,
+    cancel: &AtomicBool,
+  ) -> Result<(Self, Vec<F>, (Vec<F>, Vec<F>, F)), Cancelled>
   where
     Func: Fn(&F, &F, &F) -> F + Sync,
     G: CurveGroup<ScalarField = F>,
   {
+    <Transcript as ProofTranscript<G>>::append_protocol_name(
+      transcript,
+      Self::protocol_name(),
+      instance,
+    );
+
     let (poly_A_vec_par, poly_B_vec_par, poly_C_par) = poly_vec_par;
 
     let mut e = *claim;
@@ -47,6 +58,8 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     let mut cubic_polys: Vec<CompressedUniPoly<F>> = Vec::new();
 
     for _j in 0..num_rounds {
+      Cancelled::check(cancel)?;
+
       #[cfg(feature = "multicore")]
       let iterator = poly_A_vec_par.par_iter().zip(poly_B_vec_par.par_iter());
 
@@ -131,7 +144,7 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
       .collect();
     let claims_prod = (poly_A_par_final, poly_B_par_final, poly_C_par[0]);
 
-    (SumcheckInstanceProof::new(cubic_polys), r, claims_prod)
+    Ok((SumcheckInstanceProof::new(cubic_polys), r, claims_prod))
   }
 
   /// Create a sumcheck proof for polynomial(s) of arbitrary degree.
@@ -142,6 +155,8 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
   /// - `polys`: Dense polynomials to combine and sumcheck
   /// - `comb_func`: Function used to combine each polynomial evaluation
   /// - `transcript`: Fiat-shamir transcript
+  /// - `instance`: Which of the composed proof's sumchecks this is, see `append_protocol_name`
+  /// - `cancel`: Checked before each round, proving stops with `Cancelled` once it is set
   ///
   /// Returns (SumcheckInstanceProof, r_eval_point, final_evals)
   /// - `r_eval_point`: Final random point of evaluation
@@ -154,15 +169,21 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     comb_func: Func,
     combined_degree: usize,
     transcript: &mut T,
-  ) -> (Self, Vec<F>, Vec<F>)
+    instance: u64,
+    cancel: &AtomicBool,
+  ) -> Result<(Self, Vec<F>, Vec<F>), Cancelled>
   where
     Func: Fn(&[F; ALPHA]) -> F + Sync,
     G: CurveGroup<ScalarField = F>,
   {
+    transcript.append_protocol_name(Self::protocol_name(), instance);
+
     let mut r: Vec<F> = Vec::new();
     let mut compressed_polys: Vec<CompressedUniPoly<F>> = Vec::new();
 
     for _round in 0..num_rounds {
+      Cancelled::check(cancel)?;
+
       // Vector storing evaluations of combined polynomials g(x) = P_0(x) * ... P_{num_polys} (x)
       // for points {0, ..., |g(x)|}
       let mut eval_points = vec![F::zero(); combined_degree + 1];
@@ -256,7 +277,7 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
 
     let final_evals = polys.iter().map(|poly| poly[0]).collect();
 
-    (SumcheckInstanceProof::new(compressed_polys), r, final_evals)
+    Ok((SumcheckInstanceProof::new(compressed_polys), r, final_evals))
   }
 }
 
@@ -270,6 +291,10 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     SumcheckInstanceProof { compressed_polys }
   }
 
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::Sumcheck
+  }
+
   /// Verify this sumcheck proof.
   /// Note: Verification does not execute the final check of sumcheck protocol: g_v(r_v) = oracle_g(r),
   /// as the oracle is not passed in. Expected that the caller will implement.
@@ -279,6 +304,7 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
   /// - `num_rounds`: Number of rounds of sumcheck, or number of variables to bind
   /// - `degree_bound`: Maximum allowed degree of the combined univariate polynomial
   /// - `transcript`: Fiat-shamir transcript
+  /// - `instance`: Which of the composed proof's sumchecks this is, see `append_protocol_name`
   ///
   /// Returns (e, r)
   /// - `e`: Claimed evaluation at random point
@@ -289,15 +315,23 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
     num_rounds: usize,
     degree_bound: usize,
     transcript: &mut T,
+    instance: u64,
   ) -> Result<(F, Vec<F>), ProofVerifyError>
   where
     G: CurveGroup<ScalarField = F>,
   {
+    transcript.append_protocol_name(Self::protocol_name(), instance);
+
     let mut e = claim;
     let mut r: Vec<F> = Vec::new();
 
     // verify that there is a univariate polynomial for each round
-    assert_eq!(self.compressed_polys.len(), num_rounds);
+    if self.compressed_polys.len() != num_rounds {
+      return Err(ProofVerifyError::InvalidInputLength(
+        num_rounds,
+        self.compressed_polys.len(),
+      ));
+    }
     for i in 0..self.compressed_polys.len() {
       let poly = self.compressed_polys[i].decompress(&e);
 
@@ -310,7 +344,9 @@ impl<F: PrimeField> SumcheckInstanceProof<F> {
       }
 
       // check if G_k(0) + G_k(1) = e
-      assert_eq!(poly.eval_at_zero() + poly.eval_at_one(), e);
+      if poly.eval_at_zero() + poly.eval_at_one() != e {
+        return Err(ProofVerifyError::SumcheckRoundFailed(i));
+      }
 
       // append the prover's message to the transcript
       <UniPoly<F> as AppendToTranscript<G>>::append_to_transcript(&poly, b"poly", transcript);
@@ -345,6 +381,10 @@ impl<G: CurveGroup> ZKSumcheckInstanceProof<G> {
     }
   }
 
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::ZKSumcheck
+  }
+
   pub fn verify(
     &self,
     comm_claim: &G,
@@ -353,13 +393,28 @@ impl<G: CurveGroup> ZKSumcheckInstanceProof<G> {
     gens_1: &MultiCommitGens<G>,
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
   ) -> Result<(G, Vec<G::ScalarField>), ProofVerifyError> {
+    <Transcript as ProofTranscript<G>>::append_protocol_name(
+      transcript,
+      Self::protocol_name(),
+      instance,
+    );
+
     // verify degree bound
-    assert_eq!(gens_n.n, degree_bound + 1);
+    if gens_n.n != degree_bound + 1 {
+      return Err(ProofVerifyError::InvalidInputLength(
+        degree_bound + 1,
+        gens_n.n,
+      ));
+    }
 
     // verify that there is a univariate polynomial for each round
-    assert_eq!(self.comm_polys.len(), num_rounds);
-    assert_eq!(self.comm_evals.len(), num_rounds);
+    for len in [self.comm_polys.len(), self.comm_evals.len()] {
+      if len != num_rounds {
+        return Err(ProofVerifyError::InvalidInputLength(num_rounds, len));
+      }
+    }
 
     let mut r: Vec<G::ScalarField> = Vec::new();
     for i in 0..self.comm_polys.len() {
@@ -430,6 +485,7 @@ impl<G: CurveGroup> ZKSumcheckInstanceProof<G> {
             gens_1,
             gens_n,
             transcript,
+            nested_instance(instance, i),
             &a,
             &self.comm_polys[i],
             &comm_target,
@@ -437,7 +493,7 @@ impl<G: CurveGroup> ZKSumcheckInstanceProof<G> {
           .is_ok()
       };
       if !res {
-        return Err(ProofVerifyError::InternalError);
+        return Err(ProofVerifyError::SumcheckRoundFailed(i));
       }
 
       r.push(r_i);
@@ -493,10 +549,13 @@ mod test {
         comb_func_prod,
         3,
         &mut transcript,
-      );
+        0,
+        &AtomicBool::new(false),
+      )
+      .unwrap();
 
     let mut transcript: TestTranscript<Fr> = TestTranscript::new(r.clone(), vec![]);
-    let verify_result = proof.verify::<G1Projective, _>(claim, num_vars, 3, &mut transcript);
+    let verify_result = proof.verify::<G1Projective, _>(claim, num_vars, 3, &mut transcript, 0);
     assert!(verify_result.is_ok());
 
     let (verify_evaluation, verify_randomness) = verify_result.unwrap();
@@ -510,5 +569,12 @@ mod test {
 
     let oracle_query = a * b * c;
     assert_eq!(verify_evaluation, oracle_query);
+
+    // A wrong claim is caught in the first round
+    let mut transcript: TestTranscript<Fr> = TestTranscript::new(r.clone(), vec![]);
+    assert!(matches!(
+      proof.verify::<G1Projective, _>(claim + Fr::one(), num_vars, 3, &mut transcript, 0),
+      Err(ProofVerifyError::SumcheckRoundFailed(0))
+    ));
   }
 }
@@ -3,7 +3,7 @@
 use crate::poly::commitments::{Commitments, MultiCommitGens};
 use crate::utils::errors::ProofVerifyError;
 use crate::utils::random::RandomTape;
-use crate::utils::transcript::ProofTranscript;
+use crate::utils::transcript::{ProofTranscript, ProtocolDomain};
 use ark_ec::CurveGroup;
 use ark_serialize::*;
 use merlin::Transcript;
@@ -16,13 +16,14 @@ pub struct KnowledgeProof<G: CurveGroup> {
 }
 
 impl<G: CurveGroup> KnowledgeProof<G> {
-  fn protocol_name() -> &'static [u8] {
-    b"knowledge proof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::Knowledge
   }
 
   pub fn prove(
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
     x: &G::ScalarField,
     r: &G::ScalarField,
@@ -30,6 +31,7 @@ impl<G: CurveGroup> KnowledgeProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       KnowledgeProof::<G>::protocol_name(),
+      instance,
     );
 
     // produce two random Fs
@@ -54,11 +56,13 @@ impl<G: CurveGroup> KnowledgeProof<G> {
     &self,
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     C: &G,
   ) -> Result<(), ProofVerifyError> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       KnowledgeProof::<G>::protocol_name(),
+      instance,
     );
 
     <Transcript as ProofTranscript<G>>::append_point(transcript, b"C", C);
@@ -82,13 +86,14 @@ pub struct EqualityProof<G: CurveGroup> {
 }
 
 impl<G: CurveGroup> EqualityProof<G> {
-  fn protocol_name() -> &'static [u8] {
-    b"equality proof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::Equality
   }
 
   pub fn prove(
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
     v1: &G::ScalarField,
     s1: &G::ScalarField,
@@ -98,6 +103,7 @@ impl<G: CurveGroup> EqualityProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       EqualityProof::<G>::protocol_name(),
+      instance,
     );
 
     // produce a random F
@@ -124,12 +130,14 @@ impl<G: CurveGroup> EqualityProof<G> {
     &self,
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     C1: &G,
     C2: &G,
   ) -> Result<(), ProofVerifyError> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       EqualityProof::<G>::protocol_name(),
+      instance,
     );
 
     <Transcript as ProofTranscript<G>>::append_point(transcript, b"C1", C1);
@@ -162,13 +170,14 @@ pub struct ProductProof<G: CurveGroup> {
 }
 
 impl<G: CurveGroup> ProductProof<G> {
-  fn protocol_name() -> &'static [u8] {
-    b"product proof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::Product
   }
 
   pub fn prove(
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
     x: &G::ScalarField,
     rX: &G::ScalarField,
@@ -180,6 +189,7 @@ impl<G: CurveGroup> ProductProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       ProductProof::<G>::protocol_name(),
+      instance,
     );
 
     // produce five random F
@@ -254,6 +264,7 @@ impl<G: CurveGroup> ProductProof<G> {
     &self,
     gens_n: &MultiCommitGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     X: &G,
     Y: &G,
     Z: &G,
@@ -261,6 +272,7 @@ impl<G: CurveGroup> ProductProof<G> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       ProductProof::<G>::protocol_name(),
+      instance,
     );
 
     <Transcript as ProofTranscript<G>>::append_point(transcript, b"X", X);
@@ -323,11 +335,11 @@ mod tests {
     let mut random_tape = RandomTape::new(b"proof");
     let mut prover_transcript = Transcript::new(b"example");
     let (proof, committed_value) =
-      KnowledgeProof::<G>::prove(&gens_1, &mut prover_transcript, &mut random_tape, &x, &r);
+      KnowledgeProof::<G>::prove(&gens_1, &mut prover_transcript, 0, &mut random_tape, &x, &r);
 
     let mut verifier_transcript = Transcript::new(b"example");
     assert!(proof
-      .verify(&gens_1, &mut verifier_transcript, &committed_value)
+      .verify(&gens_1, &mut verifier_transcript, 0, &committed_value)
       .is_ok());
   }
 
@@ -350,6 +362,7 @@ mod tests {
     let (proof, C1, C2) = EqualityProof::prove(
       &gens_1,
       &mut prover_transcript,
+      0,
       &mut random_tape,
       &v1,
       &s1,
@@ -359,7 +372,7 @@ mod tests {
 
     let mut verifier_transcript = Transcript::new(b"example");
     assert!(proof
-      .verify(&gens_1, &mut verifier_transcript, &C1, &C2)
+      .verify(&gens_1, &mut verifier_transcript, 0, &C1, &C2)
       .is_ok());
   }
 
@@ -384,6 +397,7 @@ mod tests {
     let (proof, X, Y, Z) = ProductProof::prove(
       &gens_1,
       &mut prover_transcript,
+      0,
       &mut random_tape,
       &x,
       &rX,
@@ -395,7 +409,7 @@ mod tests {
 
     let mut verifier_transcript = Transcript::new(b"example");
     assert!(proof
-      .verify(&gens_1, &mut verifier_transcript, &X, &Y, &Z)
+      .verify(&gens_1, &mut verifier_transcript, 0, &X, &Y, &Z)
       .is_ok());
   }
 }
@@ -0,0 +1,144 @@
+use std::marker::PhantomData;
+
+use ark_ff::PrimeField;
+use ark_std::log2;
+
+use crate::utils::split_bits;
+
+use super::SubtableStrategy;
+
+/// A bit-by-bit boolean operation on two operands, e.g. AND, XOR, or NAND.
+pub trait BitwiseOp {
+  fn apply(x: bool, y: bool) -> bool;
+}
+
+/// Subtable strategy for any `BitwiseOp`, deriving both the materialized subtable and its MLE
+/// from the operation's truth table so that the two cannot disagree.
+///
+/// The subtable maps lhs | rhs to sum_i 2^i * op(lhs_i, rhs_i), whose MLE is
+/// sum_i 2^i * op~(x_i, y_i), where op~ is the multilinear extension of the 2-bit truth table:
+/// op~(x, y) = sum_{a, b in {0, 1}} op(a, b) * eq(a, x) * eq(b, y)
+pub struct BitwiseSubtableStrategy<O: BitwiseOp> {
+  _op: PhantomData<O>,
+}
+
+pub enum And {}
+impl BitwiseOp for And {
+  fn apply(x: bool, y: bool) -> bool {
+    x & y
+  }
+}
+
+pub enum Or {}
+impl BitwiseOp for Or {
+  fn apply(x: bool, y: bool) -> bool {
+    x | y
+  }
+}
+
+pub enum Xor {}
+impl BitwiseOp for Xor {
+  fn apply(x: bool, y: bool) -> bool {
+    x ^ y
+  }
+}
+
+impl<F: PrimeField, const C: usize, const M: usize, O: BitwiseOp> SubtableStrategy<F, C, M>
+  for BitwiseSubtableStrategy<O>
+{
+  const NUM_SUBTABLES: usize = 1;
+  const NUM_MEMORIES: usize = C;
+
+  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
+    let mut materialized: Vec<F> = Vec::with_capacity(M);
+    let bits_per_operand = (log2(M) / 2) as usize;
+
+    // Materialize table in counting order where lhs | rhs counts 0->m
+    for idx in 0..M {
+      let (lhs, rhs) = split_bits(idx, bits_per_operand);
+      let mut row = 0u64;
+      for i in 0..bits_per_operand {
+        let bit = O::apply((lhs >> i) & 1 == 1, (rhs >> i) & 1 == 1);
+        row |= (bit as u64) << i;
+      }
+      materialized.push(F::from(row));
+    }
+
+    [materialized]
+  }
+
+  fn evaluate_subtable_mle(_: usize, point: &[F]) -> F {
+    debug_assert!(point.len() % 2 == 0);
+    let b = point.len() / 2;
+    let (x, y) = point.split_at(b);
+
+    let mut result = F::zero();
+    for i in 0..b {
+      let x = x[b - i - 1];
+      let y = y[b - i - 1];
+      let mut bit = F::zero();
+      for (x_bit, eq_x) in [(false, F::one() - x), (true, x)] {
+        for (y_bit, eq_y) in [(false, F::one() - y), (true, y)] {
+          if O::apply(x_bit, y_bit) {
+            bit += eq_x * eq_y;
+          }
+        }
+      }
+      result += F::from(1u64 << (i)) * bit;
+    }
+    result
+  }
+
+  /// Combine bitwise subtable evaluations, each covering log2(M)/2 bits of the result:
+  /// T = sum_i 2^(i * log2(M)/2) * T'[i]
+  /// T'[C-1] | ... | T'[1] | T'[0]
+  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
+    let increment = log2(M) as usize / 2;
+    let mut sum = F::zero();
+    for (i, val) in vals.iter().enumerate() {
+      let weight: u64 = 1u64 << (i * increment);
+      sum += F::from(weight) * val;
+    }
+    sum
+  }
+
+  fn g_poly_degree() -> usize {
+    1
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{materialization_mle_parity_test, utils::index_to_field_bitvector};
+
+  use super::*;
+  use ark_curve25519::Fr;
+
+  pub enum Nand {}
+  impl BitwiseOp for Nand {
+    fn apply(x: bool, y: bool) -> bool {
+      !(x & y)
+    }
+  }
+
+  #[test]
+  fn table_materialization_hardcoded() {
+    const C: usize = 4;
+    const M: usize = 1 << 4;
+
+    let materialized: [Vec<Fr>; 1] =
+      <BitwiseSubtableStrategy<Nand> as SubtableStrategy<Fr, C, M>>::materialize_subtables();
+    let table: Vec<Fr> = materialized[0].clone();
+    assert_eq!(table[0], Fr::from(0b11)); // !(00 & 00)
+    assert_eq!(table[5], Fr::from(0b10)); // !(01 & 01)
+    assert_eq!(table[15], Fr::from(0b00)); // !(11 & 11)
+  }
+
+  materialization_mle_parity_test!(
+    materialization_parity,
+    BitwiseSubtableStrategy<Nand>,
+    Fr,
+    16,
+    1
+  );
+}
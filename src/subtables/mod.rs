@@ -1,4 +1,7 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::marker::{PhantomData, Sync};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
@@ -7,19 +10,23 @@ use ark_std::Zero;
 use merlin::Transcript;
 
 use crate::{
-  lasso::{densified::DensifiedRepresentation, memory_checking::GrandProducts},
+  lasso::{
+    densified::DensifiedRepresentation,
+    memory_checking::{reed_solomon_fingerprint, GrandProducts},
+  },
   poly::dense_mlpoly::{DensePolynomial, PolyCommitment, PolyCommitmentGens, PolyEvalProof},
   poly::eq_poly::EqPolynomial,
   utils::errors::ProofVerifyError,
   utils::math::Math,
   utils::random::RandomTape,
-  utils::transcript::{AppendToTranscript, ProofTranscript},
+  utils::transcript::{AppendToTranscript, ProofTranscript, ProtocolDomain},
 };
 
 #[cfg(feature = "multicore")]
 use rayon::prelude::*;
 
 pub mod and;
+pub mod bitwise;
 pub mod lt;
 pub mod or;
 pub mod range_check;
@@ -61,6 +68,18 @@ pub trait SubtableStrategy<F: PrimeField, const C: usize, const M: usize> {
     Self::g_poly_degree() + 1
   }
 
+  /// Fingerprint of a memory tuple (address, value, timestamp) used by the memory-checking
+  /// grand products. Defaults to the Reed-Solomon fingerprint `t * gamma^2 + v * gamma + a - tau`.
+  ///
+  /// Soundness: for (gamma, tau) = `r_mem_check` sampled after the prover has committed, distinct
+  /// tuples must map to distinct fingerprints except with negligible probability. The function must
+  /// also be affine in (a, v, t): the verifier applies it to the evaluations of dim_i, E_i, and
+  /// read_i/final_i at a random point and compares the result against the MLE of the
+  /// fingerprinted leaves at that same point.
+  fn fingerprint(a: &F, v: &F, t: &F, r_mem_check: &(F, F)) -> F {
+    reed_solomon_fingerprint(a, v, t, r_mem_check)
+  }
+
   fn memory_to_subtable_index(memory_index: usize) -> usize {
     assert_eq!(Self::NUM_SUBTABLES * C, Self::NUM_MEMORIES);
     assert!(memory_index < Self::NUM_MEMORIES);
@@ -92,13 +111,20 @@ pub trait SubtableStrategy<F: PrimeField, const C: usize, const M: usize> {
   }
 }
 
+/// Subtables materialized by `Subtables::shared_materialization`, keyed by
+/// (field, strategy, C, M). Each entry is filled at most once, outside the map's lock, so that
+/// materializing one strategy does not block lookups of another.
+type MaterializedSubtables =
+  HashMap<(TypeId, TypeId, usize, usize), Arc<OnceLock<Arc<dyn Any + Send + Sync>>>>;
+static MATERIALIZED_SUBTABLES: OnceLock<Mutex<MaterializedSubtables>> = OnceLock::new();
+
 pub struct Subtables<F: PrimeField, const C: usize, const M: usize, S>
 where
   S: SubtableStrategy<F, C, M>,
   [(); S::NUM_SUBTABLES]: Sized,
   [(); S::NUM_MEMORIES]: Sized,
 {
-  subtable_entries: [Vec<F>; S::NUM_SUBTABLES],
+  subtable_entries: Arc<[Vec<F>; S::NUM_SUBTABLES]>,
   pub lookup_polys: [DensePolynomial<F>; S::NUM_MEMORIES],
   pub combined_poly: DensePolynomial<F>,
   strategy: PhantomData<S>,
@@ -114,8 +140,59 @@ where
   /// Create new Subtables
   /// - `evaluations`: non-sparse evaluations of T[k] for each of the 'c'-dimensions as DensePolynomials
   pub fn new(nz: &[Vec<usize>; C], s: usize) -> Self {
+    Self::from_materialized(nz, s, Arc::new(S::materialize_subtables()))
+  }
+
+  /// `S::materialize_subtables()`, computed on first use and shared by every later caller with the
+  /// same field, strategy, C and M, including callers on other threads. Pass the result to
+  /// `SparsePolynomialEvaluationProof::prove_with_subtables`.
+  ///
+  /// The cache holds its own reference, so the subtables stay in memory after the last prover
+  /// drops its copy, until `clear_materialized` is called for the same parameters.
+  pub fn shared_materialization() -> Arc<[Vec<F>; S::NUM_SUBTABLES]>
+  where
+    S: 'static,
+  {
+    let entry = MATERIALIZED_SUBTABLES
+      .get_or_init(|| Mutex::new(HashMap::new()))
+      .lock()
+      .unwrap()
+      .entry(Self::materialized_key())
+      .or_default()
+      .clone();
+    entry
+      .get_or_init(|| Arc::new(S::materialize_subtables()))
+      .clone()
+      .downcast()
+      .expect("materialized subtables are keyed by their type")
+  }
+
+  /// Drops the cache's reference to the subtables `shared_materialization` produced for these
+  /// parameters. Copies already handed out stay valid; the next call materializes afresh.
+  pub fn clear_materialized()
+  where
+    S: 'static,
+  {
+    if let Some(cache) = MATERIALIZED_SUBTABLES.get() {
+      cache.lock().unwrap().remove(&Self::materialized_key());
+    }
+  }
+
+  fn materialized_key() -> (TypeId, TypeId, usize, usize)
+  where
+    S: 'static,
+  {
+    (TypeId::of::<F>(), TypeId::of::<S>(), C, M)
+  }
+
+  /// Like `new`, but reuses subtables already produced by `S::materialize_subtables`, so that
+  /// concurrent provers for the same strategy share a single read-only copy.
+  pub fn from_materialized(
+    nz: &[Vec<usize>; C],
+    s: usize,
+    subtable_entries: Arc<[Vec<F>; S::NUM_SUBTABLES]>,
+  ) -> Self {
     nz.iter().for_each(|nz_dim| assert_eq!(nz_dim.len(), s));
-    let subtable_entries = S::materialize_subtables();
     let lookup_polys: [DensePolynomial<F>; S::NUM_MEMORIES] =
       S::to_lookup_polys(&subtable_entries, nz, s);
     let combined_poly = DensePolynomial::merge(&lookup_polys);
@@ -150,6 +227,7 @@ where
             &dense.read[j],
             &dense.r#final[j],
             r_mem_check,
+            S::fingerprint,
           )
         })
         .collect::<Vec<_>>()
@@ -168,6 +246,7 @@ where
             &dense.read[j],
             &dense.r#final[j],
             r_mem_check,
+            S::fingerprint,
           )
         })
         .collect::<Vec<_>>()
@@ -185,32 +264,34 @@ where
 
   #[tracing::instrument(skip_all, name = "Subtables.compute_sumcheck_claim")]
   pub fn compute_sumcheck_claim(&self, eq: &EqPolynomial<F>) -> F {
-    let g_operands = self.lookup_polys.clone();
+    let g_operands = &self.lookup_polys;
     let hypercube_size = g_operands[0].len();
     g_operands
       .iter()
       .for_each(|operand| assert_eq!(operand.len(), hypercube_size));
 
-    let eq_evals = eq.evals();
+    // eq(r, k) = L[k_high] * R[k_low], so we factor L[k_high] out of each inner sum
+    // rather than materializing the full hypercube-sized eq table.
+    let (eq_L, eq_R) = eq.compute_factored_evals();
+    let R_size = eq_R.len();
+    assert_eq!(eq_L.len() * R_size, hypercube_size);
+
+    let inner_sum = |i: usize| -> F {
+      let mut sum = F::zero();
+      for (j, eq_R_j) in eq_R.iter().enumerate() {
+        let k = i * R_size + j;
+        let g_operands: [F; S::NUM_MEMORIES] = std::array::from_fn(|m| g_operands[m][k]);
+        // eq_R * g(T_1[k], ..., T_\alpha[k])
+        sum += *eq_R_j * S::combine_lookups(&g_operands);
+      }
+      eq_L[i] * sum
+    };
 
     #[cfg(feature = "multicore")]
-    let claim = (0..hypercube_size)
-      .into_par_iter()
-      .map(|k| {
-        let g_operands: [F; S::NUM_MEMORIES] = std::array::from_fn(|j| g_operands[j][k]);
-        // eq * g(T_1[k], ..., T_\alpha[k])
-        eq_evals[k] * S::combine_lookups(&g_operands)
-      })
-      .sum();
+    let claim = (0..eq_L.len()).into_par_iter().map(inner_sum).sum();
 
     #[cfg(not(feature = "multicore"))]
-    let claim = (0..hypercube_size)
-      .map(|k| {
-        let g_operands: [F; S::NUM_MEMORIES] = std::array::from_fn(|j| g_operands[j][k]);
-        // eq * g(T_1[k], ..., T_\alpha[k])
-        eq_evals[k] * S::combine_lookups(&g_operands)
-      })
-      .sum();
+    let claim = (0..eq_L.len()).map(inner_sum).sum();
 
     claim
   }
@@ -233,6 +314,7 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     evals: Vec<G::ScalarField>,
     gens: &PolyCommitmentGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
   ) -> PolyEvalProof<G> {
     assert_eq!(
@@ -274,6 +356,7 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
       None,
       gens,
       transcript,
+      instance,
       random_tape,
     );
 
@@ -288,11 +371,13 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     r: &[G::ScalarField],
     gens: &PolyCommitmentGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
     random_tape: &mut RandomTape<G>,
   ) -> Self {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       CombinedTableEvalProof::<G, C>::protocol_name(),
+      instance,
     );
 
     let evals = {
@@ -306,6 +391,7 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
       evals,
       gens,
       transcript,
+      instance,
       random_tape,
     );
 
@@ -319,6 +405,7 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     evals: Vec<G::ScalarField>,
     gens: &PolyCommitmentGens<G>,
     transcript: &mut Transcript,
+    instance: u64,
   ) -> Result<(), ProofVerifyError> {
     // append the claimed evaluations to transcript
     <Transcript as ProofTranscript<G>>::append_scalars(transcript, b"evals_ops_val", &evals);
@@ -345,7 +432,14 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
       &joint_claim_eval,
     );
 
-    proof.verify_plain(gens, transcript, &r_joint, &joint_claim_eval, comm)
+    proof.verify_plain(
+      gens,
+      transcript,
+      instance,
+      &r_joint,
+      &joint_claim_eval,
+      comm,
+    )
   }
 
   // verify evaluations of both polynomials at r
@@ -356,10 +450,12 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
     gens: &PolyCommitmentGens<G>,
     comm: &CombinedTableCommitment<G>,
     transcript: &mut Transcript,
+    instance: u64,
   ) -> Result<(), ProofVerifyError> {
     <Transcript as ProofTranscript<G>>::append_protocol_name(
       transcript,
       CombinedTableEvalProof::<G, C>::protocol_name(),
+      instance,
     );
     let mut evals = evals.to_owned();
     evals.resize(evals.len().next_power_of_two(), G::ScalarField::zero());
@@ -371,11 +467,12 @@ impl<G: CurveGroup, const C: usize> CombinedTableEvalProof<G, C> {
       evals,
       gens,
       transcript,
+      instance,
     )
   }
 
-  fn protocol_name() -> &'static [u8] {
-    b"Lasso CombinedTableEvalProof"
+  fn protocol_name() -> ProtocolDomain {
+    ProtocolDomain::CombinedTableEval
   }
 }
 
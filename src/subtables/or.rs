@@ -1,68 +1,14 @@
-use ark_ff::PrimeField;
-use ark_std::log2;
+use super::bitwise::{BitwiseSubtableStrategy, Or};
 
-use crate::utils::split_bits;
-
-use super::SubtableStrategy;
-
-pub enum OrSubtableStrategy {}
-
-impl<F: PrimeField, const C: usize, const M: usize> SubtableStrategy<F, C, M>
-  for OrSubtableStrategy
-{
-  const NUM_SUBTABLES: usize = 1;
-  const NUM_MEMORIES: usize = C;
-
-  fn materialize_subtables() -> [Vec<F>; <Self as SubtableStrategy<F, C, M>>::NUM_SUBTABLES] {
-    let mut materialized: Vec<F> = Vec::with_capacity(M);
-    let bits_per_operand = (log2(M) / 2) as usize;
-
-    // Materialize table in counting order where lhs | rhs counts 0->m
-    for idx in 0..M {
-      let (lhs, rhs) = split_bits(idx, bits_per_operand);
-      materialized.push(F::from((lhs | rhs) as u64));
-    }
-
-    [materialized]
-  }
-
-  fn evaluate_subtable_mle(_: usize, point: &[F]) -> F {
-    // (1 - (1-x)(1-y))
-    debug_assert!(point.len() % 2 == 0);
-    let b = point.len() / 2;
-    let (x, y) = point.split_at(b);
-
-    let mut result = F::zero();
-    for i in 0..b {
-      let x = x[b - i - 1];
-      let y = y[b - i - 1];
-      result += F::from(1u64 << (i)) * (F::one() - (F::one() - x) * (F::one() - y));
-    }
-    result
-  }
-
-  /// Combine AND table subtable evaluations
-  /// T = T'[0] + 2^16*T'[1] + 2^32*T'[2] + 2^48*T'[3]
-  /// T'[3] | T'[2] | T'[1] | T'[0]
-  fn combine_lookups(vals: &[F; <Self as SubtableStrategy<F, C, M>>::NUM_MEMORIES]) -> F {
-    let increment = log2(M) as usize / 2;
-    let mut sum = F::zero();
-    for (i, val) in vals.iter().enumerate() {
-      let weight: u64 = 1u64 << (i * increment);
-      sum += F::from(weight) * val;
-    }
-    sum
-  }
-
-  fn g_poly_degree() -> usize {
-    1
-  }
-}
+/// Bitwise OR of the two operands, derived from `Or`'s truth table.
+pub type OrSubtableStrategy = BitwiseSubtableStrategy<Or>;
 
 #[cfg(test)]
 mod test {
   use crate::{
-    materialization_mle_parity_test, subtables::Subtables, utils::index_to_field_bitvector,
+    materialization_mle_parity_test,
+    subtables::{SubtableStrategy, Subtables},
+    utils::index_to_field_bitvector,
   };
 
   use super::*;
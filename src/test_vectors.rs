@@ -0,0 +1,164 @@
+//! Test vectors for independent verifier implementations. A fixed Surge instance is proven
+//! deterministically and rendered, one `<kind> <value>` entry per line, as its statement, the
+//! compressed commitment and proof, and every challenge the verifier squeezes from its transcript,
+//! in order. Byte strings are hex-encoded arkworks compressed serializations.
+//!
+//! The vector is checked in at `FIXTURE`. Run the tests with `LASSO_BLESS_TEST_VECTORS=1` to
+//! regenerate it after an intentional change to the proof format or transcript.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use ark_curve25519::{EdwardsProjective as G1Projective, Fr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use merlin::Transcript;
+
+use crate::{
+  lasso::{
+    densified::DensifiedRepresentation,
+    surge::{
+      SparsePolyCommitmentGens, SparsePolynomialCommitment, SparsePolynomialEvaluationProof,
+    },
+  },
+  subtables::{lt::LTSubtableStrategy, SubtableStrategy},
+  utils::{math::Math, random::RandomTape, transcript::SQUEEZED_CHALLENGES},
+};
+
+const FIXTURE: &str = concat!(
+  env!("CARGO_MANIFEST_DIR"),
+  "/src/fixtures/surge_lt_c2_m4_s4.txt"
+);
+
+// The fixed instance
+const C: usize = 2;
+const M: usize = 4;
+const LOOKUPS: [[usize; C]; 4] = [[0, 1], [1, 2], [2, 3], [3, 0]];
+const GENS_LABEL: &[u8] = b"gens_sparse_poly";
+const TRANSCRIPT_LABEL: &[u8] = b"test_vector";
+type Proof = SparsePolynomialEvaluationProof<G1Projective, C, M, LTSubtableStrategy>;
+
+fn gens() -> SparsePolyCommitmentGens<G1Projective> {
+  let num_memories = <LTSubtableStrategy as SubtableStrategy<Fr, C, M>>::NUM_MEMORIES;
+  SparsePolyCommitmentGens::new(GENS_LABEL, C, LOOKUPS.len(), num_memories, M.log_2())
+}
+
+fn eval_point() -> Vec<Fr> {
+  vec![Fr::from(3u64), Fr::from(5u64)]
+}
+
+/// Verifies `proof` and returns the challenges squeezed from the verifier's transcript, each
+/// rendered as `<label> <hex>`.
+fn verifier_challenges(
+  proof: &Proof,
+  commitment: &SparsePolynomialCommitment<G1Projective>,
+) -> Vec<String> {
+  SQUEEZED_CHALLENGES.with(|challenges| challenges.borrow_mut().clear());
+  let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+  proof
+    .verify(commitment, &eval_point(), &gens(), &mut transcript)
+    .unwrap();
+  SQUEEZED_CHALLENGES
+    .with(|challenges| challenges.take())
+    .iter()
+    .map(|(label, challenge)| format!("{} {}", String::from_utf8_lossy(label), to_hex(challenge)))
+    .collect()
+}
+
+/// Proves the fixed instance and renders its test vector.
+fn render() -> String {
+  let mut dense =
+    DensifiedRepresentation::<Fr, C>::from_lookup_indices(&LOOKUPS.to_vec(), M.log_2());
+  let gens = gens();
+  let commitment = dense.commit::<G1Projective>(&gens);
+
+  let mut random_tape = RandomTape::new(b"proof");
+  let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+  let proof = Proof::prove(
+    &mut dense,
+    &eval_point(),
+    &gens,
+    &mut transcript,
+    &mut random_tape,
+  );
+
+  let mut vector = String::new();
+  writeln!(
+    vector,
+    "# Surge, LTSubtableStrategy over curve25519, C = {C}, M = {M}, s = {}",
+    LOOKUPS.len()
+  )
+  .unwrap();
+  writeln!(
+    vector,
+    "# generators label {:?}, transcript label {:?}",
+    String::from_utf8_lossy(GENS_LABEL),
+    String::from_utf8_lossy(TRANSCRIPT_LABEL)
+  )
+  .unwrap();
+  for lookup in LOOKUPS {
+    writeln!(vector, "lookup {lookup:?}").unwrap();
+  }
+  for r_i in eval_point() {
+    writeln!(vector, "r {}", serialized_hex(&r_i)).unwrap();
+  }
+  writeln!(vector, "commitment {}", serialized_hex(&commitment)).unwrap();
+  writeln!(vector, "proof {}", serialized_hex(&proof)).unwrap();
+  for challenge in verifier_challenges(&proof, &commitment) {
+    writeln!(vector, "challenge {challenge}").unwrap();
+  }
+  vector
+}
+
+fn serialized_hex(value: &impl CanonicalSerialize) -> String {
+  let mut bytes = Vec::new();
+  value.serialize_compressed(&mut bytes).unwrap();
+  to_hex(&bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+  (0..hex.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+    .collect()
+}
+
+/// Values of the vector's `kind` entries, in order.
+fn entries<'a>(vector: &'a str, kind: &'a str) -> Vec<&'a str> {
+  vector
+    .lines()
+    .filter_map(|line| line.strip_prefix(kind)?.strip_prefix(' '))
+    .collect()
+}
+
+#[test]
+fn surge_test_vector() {
+  let rendered = render();
+
+  let fixture = Path::new(FIXTURE);
+  if !fixture.exists() || std::env::var_os("LASSO_BLESS_TEST_VECTORS").is_some() {
+    std::fs::create_dir_all(fixture.parent().unwrap()).unwrap();
+    std::fs::write(fixture, &rendered).unwrap();
+  }
+  let vector = std::fs::read_to_string(fixture).unwrap();
+  // Proving is deterministic, so the checked-in vector is reproduced byte for byte
+  assert_eq!(
+    vector, rendered,
+    "{FIXTURE} is stale; rerun with LASSO_BLESS_TEST_VECTORS=1 if the change is intentional"
+  );
+
+  // Re-derive the challenges from the vector's own commitment and proof bytes
+  let commitment = SparsePolynomialCommitment::<G1Projective>::deserialize_compressed(
+    from_hex(entries(&vector, "commitment")[0]).as_slice(),
+  )
+  .unwrap();
+  let proof =
+    Proof::deserialize_compressed(from_hex(entries(&vector, "proof")[0]).as_slice()).unwrap();
+  assert_eq!(
+    verifier_challenges(&proof, &commitment),
+    entries(&vector, "challenge")
+  );
+}
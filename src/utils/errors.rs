@@ -1,4 +1,5 @@
 use core::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,6 +12,22 @@ pub enum ProofVerifyError {
   InternalError,
   #[error("Compressed group element failed to decompress: {0:?}")]
   DecompressionError([u8; 32]),
+  #[error("Invalid sparsity {0}, expected a power of two of at least 2")]
+  InvalidSparsity(usize),
+  #[error("Insufficient security, expected at least {0} bits but got {1}")]
+  InsufficientSecurity(usize, usize),
+  #[error("Primary sumcheck's final claim does not match eq(r, r_z) * g(E(r_z))")]
+  PrimarySumcheckFailed,
+  #[error("Product layer multiset equality check failed for memory {0}")]
+  ProductLayerFailed(usize),
+  #[error("Hash layer fingerprint check failed for the {1} multiset of memory {0}")]
+  HashLayerFailed(usize, &'static str),
+  #[error("Sumcheck round {0} failed: G_k(0) + G_k(1) does not match the running claim")]
+  SumcheckRoundFailed(usize),
+  #[error("Grand product layer {0} failed: final sumcheck claim does not match the layer claims")]
+  GrandProductLayerFailed(usize),
+  #[error("Transcript diverged from the prover's at the {0} checkpoint")]
+  TranscriptDigestMismatch(&'static str),
 }
 
 impl Default for ProofVerifyError {
@@ -18,3 +35,19 @@ impl Default for ProofVerifyError {
     ProofVerifyError::InternalError
   }
 }
+
+/// Returned by the cancellable provers once their cancellation flag has been set.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Proving was cancelled")]
+pub struct Cancelled;
+
+impl Cancelled {
+  /// Checked between rounds of the proving loops, so a set flag stops the prover promptly.
+  pub fn check(cancel: &AtomicBool) -> Result<(), Cancelled> {
+    if cancel.load(Ordering::Relaxed) {
+      Err(Cancelled)
+    } else {
+      Ok(())
+    }
+  }
+}
@@ -6,7 +6,7 @@ use ark_std::test_rng;
 use merlin::Transcript;
 use rand_chacha::rand_core::RngCore;
 
-use crate::utils::transcript::ProofTranscript;
+use crate::utils::transcript::{ProofTranscript, ProtocolDomain};
 
 pub fn gen_random_points<F: PrimeField, const C: usize>(memory_bits: usize) -> [Vec<F>; C] {
   std::array::from_fn(|_| gen_random_point(memory_bits))
@@ -84,10 +84,13 @@ impl<G: CurveGroup> ProofTranscript<G> for TestTranscript<G::ScalarField> {
     self.merlin_transcript.append_u64(label, x);
   }
 
-  fn append_protocol_name(&mut self, protocol_name: &'static [u8]) {
+  fn append_protocol_name(&mut self, protocol_name: ProtocolDomain, instance: u64) {
     self
       .merlin_transcript
-      .append_message(b"protocol-name", protocol_name);
+      .append_message(b"protocol-name", protocol_name.label());
+    self
+      .merlin_transcript
+      .append_u64(b"protocol-instance", instance);
   }
 
   fn append_scalar(&mut self, label: &'static [u8], scalar: &G::ScalarField) {
@@ -97,15 +100,14 @@ impl<G: CurveGroup> ProofTranscript<G> for TestTranscript<G::ScalarField> {
   }
 
   fn append_scalars(&mut self, label: &'static [u8], scalars: &[G::ScalarField]) {
-    self
-      .merlin_transcript
-      .append_message(label, b"begin_append_vector");
+    let mut buf = vec![];
     for item in scalars.iter() {
-      <Self as ProofTranscript<G>>::append_scalar(self, label, item);
+      item.serialize_compressed(&mut buf).unwrap();
     }
     self
       .merlin_transcript
-      .append_message(label, b"end_append_vector");
+      .append_u64(label, scalars.len() as u64);
+    self.merlin_transcript.append_message(label, &buf);
   }
 
   fn append_point(&mut self, label: &'static [u8], point: &G) {
@@ -3,12 +3,57 @@ use ark_ff::PrimeField;
 use ark_serialize::CanonicalSerialize;
 use merlin::Transcript;
 
+/// Defines `ProtocolDomain` together with its `ALL` list and labels from a single table.
+macro_rules! protocol_domains {
+  ($($domain:ident => $label:expr),+ $(,)?) => {
+    /// Domain separators for every subprotocol that appends to a transcript. Each subprotocol
+    /// appends its domain and an instance number via `append_protocol_name` before any of its own
+    /// messages, so message labels shared between subprotocols (e.g. `b"poly"`,
+    /// `b"challenge_nextround"`) are always preceded by a distinct (domain, instance) pair when
+    /// proofs are composed, including when one subprotocol runs several times in a row.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ProtocolDomain {
+      $($domain),+
+    }
+
+    impl ProtocolDomain {
+      /// Every domain, generated from the same list as the enum so that none can be left out.
+      pub const ALL: &'static [ProtocolDomain] = &[$(ProtocolDomain::$domain),+];
+
+      pub fn label(&self) -> &'static [u8] {
+        match self {
+          $(ProtocolDomain::$domain => $label),+
+        }
+      }
+    }
+  };
+}
+
+protocol_domains! {
+  SparsePolynomialEvaluation => b"Lasso SparsePolynomialEvaluationProof",
+  MemoryChecking => b"Lasso MemoryCheckingProof",
+  HashLayer => b"Lasso HashLayerProof",
+  ProductLayer => b"Lasso ProductLayerProof",
+  CombinedTableEval => b"Lasso CombinedTableEvalProof",
+  BatchedGrandProduct => b"Lasso BatchedGrandProductArgument",
+  Sumcheck => b"Lasso SumcheckInstanceProof",
+  ZKSumcheck => b"Lasso ZKSumcheckInstanceProof",
+  PolyEval => b"polynomial evaluation proof",
+  DotProduct => b"dot product proof",
+  DotProductLog => b"dot product proof (log)",
+  Knowledge => b"knowledge proof",
+  Equality => b"equality proof",
+  Product => b"product proof",
+}
+
 pub trait ProofTranscript<G: CurveGroup> {
   // Pass through to Merlin::Transcript
   fn append_message(&mut self, label: &'static [u8], msg: &'static [u8]);
   fn append_u64(&mut self, label: &'static [u8], point: u64);
 
-  fn append_protocol_name(&mut self, protocol_name: &'static [u8]);
+  /// Opens `protocol_name`'s domain for the run numbered `instance`. Callers number the runs of a
+  /// subprotocol so that no (domain, instance) pair repeats within one composed proof.
+  fn append_protocol_name(&mut self, protocol_name: ProtocolDomain, instance: u64);
   fn append_scalar(&mut self, label: &'static [u8], scalar: &G::ScalarField);
   fn append_scalars(&mut self, label: &'static [u8], scalars: &[G::ScalarField]);
   fn append_point(&mut self, label: &'static [u8], point: &G);
@@ -26,8 +71,12 @@ impl<G: CurveGroup> ProofTranscript<G> for Transcript {
     self.append_u64(label, x);
   }
 
-  fn append_protocol_name(&mut self, protocol_name: &'static [u8]) {
-    self.append_message(b"protocol-name", protocol_name);
+  fn append_protocol_name(&mut self, protocol_name: ProtocolDomain, instance: u64) {
+    #[cfg(test)]
+    APPENDED_DOMAINS.with(|domains| domains.borrow_mut().push((protocol_name, instance)));
+
+    self.append_message(b"protocol-name", protocol_name.label());
+    self.append_u64(b"protocol-instance", instance);
   }
 
   fn append_scalar(&mut self, label: &'static [u8], scalar: &G::ScalarField) {
@@ -36,12 +85,15 @@ impl<G: CurveGroup> ProofTranscript<G> for Transcript {
     self.append_message(label, &buf);
   }
 
+  /// Serializes all of `scalars` into a single buffer and appends it as one message,
+  /// prefixed by the vector length, rather than appending each scalar individually.
   fn append_scalars(&mut self, label: &'static [u8], scalars: &[G::ScalarField]) {
-    self.append_message(label, b"begin_append_vector");
+    let mut buf = vec![];
     for item in scalars.iter() {
-      <Self as ProofTranscript<G>>::append_scalar(self, label, item);
+      item.serialize_compressed(&mut buf).unwrap();
     }
-    self.append_message(label, b"end_append_vector");
+    self.append_u64(label, scalars.len() as u64);
+    self.append_message(label, &buf);
   }
 
   fn append_point(&mut self, label: &'static [u8], point: &G) {
@@ -61,7 +113,16 @@ impl<G: CurveGroup> ProofTranscript<G> for Transcript {
   fn challenge_scalar(&mut self, label: &'static [u8]) -> G::ScalarField {
     let mut buf = [0u8; 64];
     self.challenge_bytes(label, &mut buf);
-    G::ScalarField::from_le_bytes_mod_order(&buf)
+    let challenge = G::ScalarField::from_le_bytes_mod_order(&buf);
+
+    #[cfg(test)]
+    SQUEEZED_CHALLENGES.with(|challenges| {
+      let mut bytes = vec![];
+      challenge.serialize_compressed(&mut bytes).unwrap();
+      challenges.borrow_mut().push((label, bytes));
+    });
+
+    challenge
   }
 
   fn challenge_vector(&mut self, label: &'static [u8], len: usize) -> Vec<G::ScalarField> {
@@ -71,6 +132,53 @@ impl<G: CurveGroup> ProofTranscript<G> for Transcript {
   }
 }
 
+/// Instance number for run `index` of a subprotocol nested in run `parent` of another, e.g. the
+/// sumcheck of one grand-product layer. Nested instances never collide with top-level instances,
+/// which stay below 2^32, nor with instances nested in a different parent.
+pub fn nested_instance(parent: u64, index: usize) -> u64 {
+  ((parent + 1) << 32) | index as u64
+}
+
+#[cfg(test)]
+thread_local! {
+  /// Every (domain, instance) pair appended to a merlin transcript on this thread, in order, so
+  /// that tests can check the domain separation of composed proofs.
+  pub(crate) static APPENDED_DOMAINS: std::cell::RefCell<Vec<(ProtocolDomain, u64)>> =
+    std::cell::RefCell::new(Vec::new());
+
+  /// Every scalar challenge squeezed from a merlin transcript on this thread, in order, as its
+  /// label and compressed serialization.
+  pub(crate) static SQUEEZED_CHALLENGES: std::cell::RefCell<Vec<(&'static [u8], Vec<u8>)>> =
+    std::cell::RefCell::new(Vec::new());
+}
+
+/// Squeezes a 32-byte digest of the transcript state, binding every message appended so far.
+/// Squeezing advances the transcript like any other challenge, so prover and verifier must take
+/// digests at the same points.
+pub fn transcript_digest(transcript: &mut Transcript, label: &'static [u8]) -> [u8; 32] {
+  let mut digest = [0u8; 32];
+  transcript.challenge_bytes(label, &mut digest);
+  digest
+}
+
 pub trait AppendToTranscript<G: CurveGroup> {
   fn append_to_transcript<T: ProofTranscript<G>>(&self, label: &'static [u8], transcript: &mut T);
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn protocol_domains_are_unique() {
+    for (i, domain) in ProtocolDomain::ALL.iter().enumerate() {
+      for other in ProtocolDomain::ALL.iter().skip(i + 1) {
+        assert_ne!(
+          domain.label(),
+          other.label(),
+          "{domain:?} and {other:?} share a transcript label"
+        );
+      }
+    }
+  }
+}